@@ -0,0 +1,403 @@
+//! A B-tree
+
+#![forbid(unsafe_code, missing_debug_implementations)]
+
+use std::fmt::{self, Debug, Display};
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+use ordered_map::OrderedMap;
+
+// Default order, e.g. the maximum number of children of a node.
+const ORDER: usize = 6;
+
+/// An ordered map based on the in-memory B-tree.
+#[derive(Debug)]
+pub struct BTree<K, V> {
+    root: Node<K, V>,
+    order: usize,
+    len: usize,
+}
+
+impl<K, V> Default for BTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V> Display for BTree<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.root.print_depth_first(f, 0)
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for BTree<K, V> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert(key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+
+    fn range<R: RangeBounds<K>>(&self, range: R) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(self.range(range))
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K: Ord, V> BTree<K, V> {
+    /// Inserts the key/value and returns the old value in case
+    /// the key was already in the map.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = self.root.insert(key, value, self.order);
+        if self.root.keys.len() >= self.order {
+            // grows the tree by splitting the root.
+            let (key, value, right) = self.root.split();
+            let left = mem::take(&mut self.root);
+            self.root = Node {
+                keys: vec![key],
+                values: vec![value],
+                children: vec![left, right],
+            };
+        }
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Removes the key and returns the value in case it was in the map.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.root.remove(key, self.min_keys())?;
+        if self.root.keys.is_empty() {
+            // shrinks the tree by dropping the empty root.
+            if let Some(child) = self.root.children.pop() {
+                self.root = child;
+            }
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut node = &self.root;
+        loop {
+            match node.keys.binary_search(key) {
+                Ok(i) => return Some(&node.values[i]),
+                Err(i) => node = node.children.get(i)?,
+            }
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the in-order iterator over the entries within the range.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V> {
+        // the entries from the start bound.
+        let mut stack = Vec::new();
+        let mut node = Some(&self.root);
+        while let Some(n) = node {
+            let i = n
+                .keys
+                .partition_point(|k| !is_after(range.start_bound(), k));
+            stack.push((n, i));
+            node = n.children.get(i);
+        }
+
+        // the last entry before the end bound.
+        let mut last = None;
+        let mut node = Some(&self.root);
+        while let Some(n) = node {
+            let i = n.keys.partition_point(|k| is_before(range.end_bound(), k));
+            if i > 0 {
+                last = Some(&n.keys[i - 1]);
+            }
+            node = n.children.get(i);
+        }
+
+        let mut range = Range { stack, last };
+        match (range.peek(), last) {
+            (Some(first), Some(last)) if first <= last => range,
+            _ => Range {
+                stack: Vec::new(),
+                last: None,
+            },
+        }
+    }
+}
+
+impl<K, V> BTree<K, V> {
+    pub fn new() -> Self {
+        Self::with_order(ORDER)
+    }
+
+    /// Creates a B-tree of which node holds up to `order` children.
+    ///
+    /// # Panics
+    ///
+    /// It panics when the `order` is less than 3.
+    pub fn with_order(order: usize) -> Self {
+        assert!(order >= 3, "too small order: {order}");
+        Self {
+            root: Node::default(),
+            order,
+            len: 0,
+        }
+    }
+
+    pub const fn order(&self) -> usize {
+        self.order
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The minimum number of keys in the non-root node.
+    const fn min_keys(&self) -> usize {
+        (self.order - 1) / 2
+    }
+}
+
+/// An in-order iterator over the entries of [`BTree`] within a range.
+#[derive(Debug)]
+pub struct Range<'a, K, V> {
+    /// The nodes and the index of the next key in the node.
+    stack: Vec<(&'a Node<K, V>, usize)>,
+    last: Option<&'a K>,
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = self.last?;
+        let key = self.peek()?;
+        let (node, i) = self.stack.last_mut()?;
+        let (node, i) = (*node, mem::replace(i, *i + 1));
+        if std::ptr::eq(key, last) {
+            self.last = None;
+        }
+        // the entries between this key and the next.
+        let mut child = node.children.get(i + 1);
+        while let Some(n) = child {
+            self.stack.push((n, 0));
+            child = n.children.first();
+        }
+        Some((&node.keys[i], &node.values[i]))
+    }
+}
+
+impl<'a, K, V> Range<'a, K, V> {
+    /// Returns the next key, dropping the visited nodes.
+    fn peek(&mut self) -> Option<&'a K> {
+        while let Some(&(node, i)) = self.stack.last() {
+            if i < node.keys.len() {
+                return Some(&node.keys[i]);
+            }
+            self.stack.pop();
+        }
+        None
+    }
+}
+
+/// Returns `true` when the key is after the start bound.
+fn is_after<K: Ord>(bound: Bound<&K>, key: &K) -> bool {
+    match bound {
+        Bound::Included(start) => key >= start,
+        Bound::Excluded(start) => key > start,
+        Bound::Unbounded => true,
+    }
+}
+
+/// Returns `true` when the key is before the end bound.
+fn is_before<K: Ord>(bound: Bound<&K>, key: &K) -> bool {
+    match bound {
+        Bound::Included(end) => key <= end,
+        Bound::Excluded(end) => key < end,
+        Bound::Unbounded => true,
+    }
+}
+
+#[derive(Debug)]
+struct Node<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    /// No children for the leaf node.
+    children: Vec<Self>,
+}
+
+impl<K, V> Default for Node<K, V> {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            values: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+impl<K: Ord, V> Node<K, V> {
+    fn insert(&mut self, key: K, value: V, order: usize) -> Option<V> {
+        match self.keys.binary_search(&key) {
+            Ok(i) => Some(mem::replace(&mut self.values[i], value)),
+            Err(i) if self.is_leaf() => {
+                self.keys.insert(i, key);
+                self.values.insert(i, value);
+                None
+            }
+            Err(i) => {
+                let old = self.children[i].insert(key, value, order);
+                if self.children[i].keys.len() >= order {
+                    let (key, value, right) = self.children[i].split();
+                    self.keys.insert(i, key);
+                    self.values.insert(i, value);
+                    self.children.insert(i + 1, right);
+                }
+                old
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K, min: usize) -> Option<V> {
+        match self.keys.binary_search(key) {
+            Ok(i) if self.is_leaf() => {
+                self.keys.remove(i);
+                Some(self.values.remove(i))
+            }
+            Ok(i) => {
+                // replaces the entry with the previous one.
+                let (key, value) = self.children[i].remove_max(min);
+                self.keys[i] = key;
+                let value = mem::replace(&mut self.values[i], value);
+                self.fill(i, min);
+                Some(value)
+            }
+            Err(_) if self.is_leaf() => None,
+            Err(i) => {
+                let value = self.children[i].remove(key, min)?;
+                self.fill(i, min);
+                Some(value)
+            }
+        }
+    }
+}
+
+impl<K, V> Node<K, V> {
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Splits the node in half and returns the middle entry and
+    /// the right half.
+    fn split(&mut self) -> (K, V, Self) {
+        let mid = self.keys.len() / 2;
+        let right = Self {
+            keys: self.keys.split_off(mid + 1),
+            values: self.values.split_off(mid + 1),
+            children: if self.is_leaf() {
+                Vec::new()
+            } else {
+                self.children.split_off(mid + 1)
+            },
+        };
+        let key = self.keys.pop().unwrap();
+        let value = self.values.pop().unwrap();
+        (key, value, right)
+    }
+
+    fn remove_max(&mut self, min: usize) -> (K, V) {
+        if self.is_leaf() {
+            let key = self.keys.pop().unwrap();
+            let value = self.values.pop().unwrap();
+            return (key, value);
+        }
+        let i = self.children.len() - 1;
+        let max = self.children[i].remove_max(min);
+        self.fill(i, min);
+        max
+    }
+
+    /// Fills the `i`th child up to `min` keys, either by borrowing
+    /// the key from the sibling or by merging with it.
+    fn fill(&mut self, i: usize, min: usize) {
+        if self.children[i].keys.len() >= min {
+            return;
+        }
+        if i > 0 && self.children[i - 1].keys.len() > min {
+            self.rotate_right(i - 1);
+        } else if i + 1 < self.children.len() && self.children[i + 1].keys.len() > min {
+            self.rotate_left(i);
+        } else if i > 0 {
+            self.merge(i - 1);
+        } else {
+            self.merge(i);
+        }
+    }
+
+    /// Moves the last entry of the `i`th child to the `i + 1`th child
+    /// through the `i`th key.
+    fn rotate_right(&mut self, i: usize) {
+        let (left, right) = self.children.split_at_mut(i + 1);
+        let (left, right) = (&mut left[i], &mut right[0]);
+        let key = mem::replace(&mut self.keys[i], left.keys.pop().unwrap());
+        let value = mem::replace(&mut self.values[i], left.values.pop().unwrap());
+        right.keys.insert(0, key);
+        right.values.insert(0, value);
+        if let Some(child) = left.children.pop() {
+            right.children.insert(0, child);
+        }
+    }
+
+    /// Moves the first entry of the `i + 1`th child to the `i`th child
+    /// through the `i`th key.
+    fn rotate_left(&mut self, i: usize) {
+        let (left, right) = self.children.split_at_mut(i + 1);
+        let (left, right) = (&mut left[i], &mut right[0]);
+        let key = mem::replace(&mut self.keys[i], right.keys.remove(0));
+        let value = mem::replace(&mut self.values[i], right.values.remove(0));
+        left.keys.push(key);
+        left.values.push(value);
+        if !right.is_leaf() {
+            left.children.push(right.children.remove(0));
+        }
+    }
+
+    /// Merges the `i + 1`th child into the `i`th child with the `i`th key.
+    fn merge(&mut self, i: usize) {
+        let right = self.children.remove(i + 1);
+        let left = &mut self.children[i];
+        left.keys.push(self.keys.remove(i));
+        left.values.push(self.values.remove(i));
+        left.keys.extend(right.keys);
+        left.values.extend(right.values);
+        left.children.extend(right.children);
+    }
+}
+
+impl<K: Debug, V> Node<K, V> {
+    fn print_depth_first(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        writeln!(f, "{:.<depth$}{:?}", "", self.keys)?;
+        for child in &self.children {
+            child.print_depth_first(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
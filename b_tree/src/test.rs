@@ -0,0 +1,74 @@
+use super::{BTree, Node};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Checks the B-tree invariants and returns the depth of the leaves.
+fn check_invariants<K: Ord, V>(node: &Node<K, V>, order: usize, root: bool) -> usize {
+    assert!(
+        node.keys.len() < order,
+        "too many keys: {}",
+        node.keys.len()
+    );
+    if !root {
+        assert!(node.keys.len() >= (order - 1) / 2, "too few keys");
+    }
+    assert_eq!(node.keys.len(), node.values.len());
+    assert!(node.keys.windows(2).all(|w| w[0] < w[1]), "unsorted keys");
+    if node.is_leaf() {
+        return 1;
+    }
+    assert_eq!(node.children.len(), node.keys.len() + 1);
+    for (i, key) in node.keys.iter().enumerate() {
+        assert!(node.children[i].keys.iter().all(|k| k < key));
+        assert!(node.children[i + 1].keys.iter().all(|k| k > key));
+    }
+    let depth = check_invariants(&node.children[0], order, false);
+    for child in &node.children[1..] {
+        assert_eq!(check_invariants(child, order, false), depth, "unbalanced");
+    }
+    depth + 1
+}
+
+#[test]
+fn test_ordered_map_conformance() {
+    ordered_map::conformance::check(BTree::new);
+    for order in [3, 4, 5, 32] {
+        ordered_map::conformance::check(|| BTree::with_order(order));
+    }
+}
+
+#[test]
+fn test_invariants() {
+    for order in [3, 4, 5, 6, 7] {
+        let mut rng = StdRng::seed_from_u64(order as u64);
+        let mut map = BTree::with_order(order);
+
+        for _ in 0..5_000 {
+            let k = rng.gen_range(0..500);
+            if rng.gen_bool(0.6) {
+                map.insert(k, k);
+            } else {
+                map.remove(&k);
+            }
+            check_invariants(&map.root, order, true);
+        }
+    }
+}
+
+#[test]
+fn test_depth() {
+    let mut map = BTree::with_order(3);
+    for k in 0..1_023 {
+        map.insert(k, ());
+    }
+    assert_eq!(map.order(), 3);
+    // at least two children per node.
+    assert!(check_invariants(&map.root, 3, true) <= 10);
+}
+
+#[test]
+#[should_panic]
+fn test_too_small_order() {
+    BTree::<i32, i32>::with_order(2);
+}
@@ -0,0 +1,179 @@
+//! An interval tree
+
+use std::ops::{Bound, Range};
+
+use crate::{Augment, AvlMap, Node, Tree};
+
+/// An interval tree mapping the half-open intervals to the values.
+///
+/// It's the [`AvlMap`] keyed by the interval start and end, of which
+/// nodes keep the max end of the subtree to skip the subtrees without
+/// any overlapping intervals, e.g. the queries take O((k + 1) log n) for
+/// the `k` intervals found, as each of them may take a walk down
+/// the tree past the intervals ending before the query.
+#[derive(Debug)]
+pub struct IntervalTree<T, V> {
+    map: AvlMap<(T, T), V, MaxEnd<T>>,
+}
+
+/// The max end of the intervals in the subtree.
+#[derive(Clone, Debug, PartialEq)]
+struct MaxEnd<T>(T);
+
+impl<T: Ord + Clone, V> Augment<(T, T), V> for MaxEnd<T> {
+    fn from_entry(key: &(T, T), _value: &V) -> Self {
+        Self(key.1.clone())
+    }
+
+    fn combine(&self, right: &Self) -> Self {
+        Self(std::cmp::max(&self.0, &right.0).clone())
+    }
+}
+
+impl<T, V> Default for IntervalTree<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone, V> IntervalTree<T, V> {
+    /// Inserts the interval and returns the old value in case
+    /// the same interval was already in the tree.
+    ///
+    /// # Panics
+    ///
+    /// It panics with the empty interval.
+    pub fn insert(&mut self, interval: Range<T>, value: V) -> Option<V> {
+        assert!(interval.start < interval.end, "empty interval");
+        self.map.insert((interval.start, interval.end), value)
+    }
+
+    /// Removes the interval and returns the value in case it was in the tree.
+    pub fn remove(&mut self, interval: &Range<T>) -> Option<V> {
+        self.map
+            .remove(&(interval.start.clone(), interval.end.clone()))
+    }
+
+    pub fn get(&self, interval: &Range<T>) -> Option<&V> {
+        self.map
+            .get(&(interval.start.clone(), interval.end.clone()))
+    }
+
+    /// Returns the intervals overlapping with the `interval` in the order
+    /// of the start, which are none for the empty or reversed `interval`.
+    pub fn overlapping(&self, interval: Range<T>) -> Overlaps<'_, T, V> {
+        let empty = interval.start >= interval.end;
+        let end = Bound::Excluded(interval.end);
+        if empty {
+            return Overlaps {
+                stack: Vec::new(),
+                start: interval.start,
+                end,
+                #[cfg(test)]
+                visited: 0,
+            };
+        }
+        Overlaps::new(&self.map.root, interval.start, end)
+    }
+
+    /// Returns the intervals containing the `point` in the order
+    /// of the start.
+    pub fn stabbing(&self, point: T) -> Overlaps<'_, T, V> {
+        Overlaps::new(&self.map.root, point.clone(), Bound::Included(point))
+    }
+}
+
+impl<T, V> IntervalTree<T, V> {
+    pub const fn new() -> Self {
+        Self {
+            map: AvlMap::augmented(),
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns the intervals in the order of the start.
+    pub fn iter(&self) -> impl Iterator<Item = (Range<&T>, &V)> {
+        self.map
+            .iter()
+            .map(|((start, end), value)| (start..end, value))
+    }
+}
+
+/// An iterator over the intervals of [`IntervalTree`] overlapping
+/// with the range.
+#[derive(Debug)]
+pub struct Overlaps<'a, T, V> {
+    stack: Vec<&'a Node<(T, T), V, MaxEnd<T>>>,
+    /// The intervals should end after `start`.
+    start: T,
+    /// And should start before `end`.
+    end: Bound<T>,
+    /// The number of the nodes visited so far.
+    #[cfg(test)]
+    visited: usize,
+}
+
+impl<'a, T: Ord, V> Overlaps<'a, T, V> {
+    fn new(tree: &'a Tree<(T, T), V, MaxEnd<T>>, start: T, end: Bound<T>) -> Self {
+        let mut overlaps = Self {
+            stack: Vec::new(),
+            start,
+            end,
+            #[cfg(test)]
+            visited: 0,
+        };
+        overlaps.push_left(tree);
+        overlaps
+    }
+
+    /// Pushes the left spine of the tree, skipping the subtrees of which
+    /// intervals all end before the start.
+    fn push_left(&mut self, mut tree: &'a Tree<(T, T), V, MaxEnd<T>>) {
+        while let Some(ref node) = tree.0 {
+            if node.aug.0 <= self.start {
+                break;
+            }
+            self.stack.push(node);
+            tree = &node.left;
+        }
+    }
+}
+
+impl<'a, T: Ord, V> Iterator for Overlaps<'a, T, V> {
+    type Item = (Range<&'a T>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            #[cfg(test)]
+            {
+                self.visited += 1;
+            }
+            let (ref start, ref end) = node.key;
+            let before_end = match self.end {
+                Bound::Included(ref end) => start <= end,
+                Bound::Excluded(ref end) => start < end,
+                Bound::Unbounded => true,
+            };
+            if !before_end {
+                // the rest starts after the end.
+                self.stack.clear();
+                return None;
+            }
+            self.push_left(&node.right);
+            if *end > self.start {
+                return Some((start..end, &node.value));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,126 @@
+use super::IntervalTree;
+
+use std::ops::Range;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn scan(
+    intervals: &[(Range<i32>, i32)],
+    hit: impl Fn(&Range<i32>) -> bool,
+) -> Vec<(Range<&i32>, &i32)> {
+    let mut found: Vec<_> = intervals
+        .iter()
+        .filter(|(interval, _)| hit(interval))
+        .map(|(interval, value)| (&interval.start..&interval.end, value))
+        .collect();
+    found.sort_by_key(|(interval, _)| (interval.start, interval.end));
+    found
+}
+
+fn names<'a>(found: impl Iterator<Item = (Range<&'a i32>, &'a &'static str)>) -> Vec<&'static str> {
+    found.map(|(_, name)| *name).collect()
+}
+
+#[test]
+fn test_overlapping() {
+    let mut tree = IntervalTree::new();
+    tree.insert(9..12, "meeting");
+    tree.insert(12..13, "lunch");
+    tree.insert(13..17, "work");
+    tree.insert(8..18, "office");
+    assert_eq!(tree.len(), 4);
+
+    assert_eq!(
+        names(tree.overlapping(11..13)),
+        vec!["office", "meeting", "lunch"]
+    );
+    assert_eq!(names(tree.overlapping(17..20)), vec!["office"]);
+    assert_eq!(names(tree.overlapping(18..20)), Vec::<&str>::new());
+    assert_eq!(names(tree.stabbing(12)), vec!["office", "lunch"]);
+    assert_eq!(names(tree.stabbing(7)), Vec::<&str>::new());
+
+    assert_eq!(tree.insert(12..13, "nap"), Some("lunch"));
+    assert_eq!(tree.get(&(12..13)), Some(&"nap"));
+    assert_eq!(tree.remove(&(8..18)), Some("office"));
+    assert_eq!(tree.remove(&(8..18)), None);
+    assert_eq!(names(tree.stabbing(12)), vec!["nap"]);
+}
+
+#[test]
+#[should_panic(expected = "empty interval")]
+fn test_empty_interval() {
+    IntervalTree::new().insert(3..3, ());
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_empty_query() {
+    let mut tree = IntervalTree::new();
+    tree.insert(0..10, "day");
+    tree.insert(4..8, "shift");
+    assert_eq!(names(tree.overlapping(5..5)), Vec::<&str>::new());
+    assert_eq!(names(tree.overlapping(7..3)), Vec::<&str>::new());
+    assert_eq!(names(tree.overlapping(5..6)), vec!["day", "shift"]);
+}
+
+#[test]
+fn test_against_brute_force() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut tree = IntervalTree::new();
+    let mut intervals: Vec<(Range<i32>, i32)> = Vec::new();
+
+    for i in 0..5_000 {
+        let start = rng.gen_range(0..1_000);
+        let end = start + rng.gen_range(1..100);
+        if rng.gen_bool(0.3) && !intervals.is_empty() {
+            let (interval, value) = intervals.swap_remove(rng.gen_range(0..intervals.len()));
+            assert_eq!(tree.remove(&interval), Some(value));
+        } else if tree.get(&(start..end)).is_none() {
+            tree.insert(start..end, i);
+            intervals.push((start..end, i));
+        }
+        tree.map.check_invariants();
+
+        let query = start..end;
+        assert_eq!(
+            tree.overlapping(query.clone()).collect::<Vec<_>>(),
+            scan(&intervals, |interval| interval.start < query.end
+                && query.start < interval.end)
+        );
+        assert_eq!(
+            tree.stabbing(start).collect::<Vec<_>>(),
+            scan(&intervals, |interval| interval.contains(&start))
+        );
+    }
+    assert_eq!(tree.len(), intervals.len());
+    assert_eq!(tree.iter().count(), intervals.len());
+}
+
+#[test]
+fn test_visited_nodes() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut tree = IntervalTree::new();
+    for i in 0..10_000 {
+        // mostly short intervals, and a few long ones.
+        let start = rng.gen_range(0..100_000);
+        let len = if i % 100 == 0 {
+            10_000
+        } else {
+            rng.gen_range(1..20)
+        };
+        tree.insert(start..start + len, i);
+    }
+    // the nodes visited by the query are bound by O((k + 1) log n).
+    let height = tree.map.height();
+    for _ in 0..1_000 {
+        let start = rng.gen_range(0..100_000);
+        let mut overlaps = tree.overlapping(start..start + rng.gen_range(1..50));
+        let k = overlaps.by_ref().count();
+        assert!(
+            overlaps.visited <= (k + 1) * height,
+            "{} for {k}",
+            overlaps.visited
+        );
+    }
+}
@@ -0,0 +1,1007 @@
+//! A balanced binary tree
+
+#![forbid(unsafe_code, missing_debug_implementations)]
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+use ordered_map::OrderedMap;
+use render::Render;
+
+pub use interval::{IntervalTree, Overlaps};
+pub use persistent::{Change, Diff, PersistentMap};
+
+mod interval;
+mod persistent;
+
+/// A summary of the subtree kept in each node of [`AvlMap`], e.g. the sum
+/// or the min/max of the values, for the range aggregate queries.
+pub trait Augment<K, V>: Clone {
+    /// Summarizes the single entry.
+    fn from_entry(key: &K, value: &V) -> Self;
+
+    /// Combines the summaries of the adjacent entries, `self` on the left.
+    fn combine(&self, right: &Self) -> Self;
+}
+
+impl<K, V> Augment<K, V> for () {
+    fn from_entry(_key: &K, _value: &V) -> Self {}
+
+    fn combine(&self, _right: &Self) -> Self {}
+}
+
+/// An ordered map based on the AVL tree.
+///
+/// Each node keeps the size of its subtree for the order statistic
+/// queries, e.g. [`select`](Self::select) and [`rank`](Self::rank), as
+/// well as the [`Augment`] summary `A` for [`aggregate`](Self::aggregate).
+#[derive(Debug)]
+pub struct AvlMap<K, V, A = ()> {
+    root: Tree<K, V, A>,
+}
+
+impl<K, V, A> Default for AvlMap<K, V, A> {
+    fn default() -> Self {
+        Self { root: Tree::new() }
+    }
+}
+
+impl<K: Debug, V: Debug, A> Display for AvlMap<K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.root.print_depth_first(f, 0)
+    }
+}
+
+impl<'a, K, V, A> IntoIterator for &'a AvlMap<K, V, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> OrderedMap<K, V> for AvlMap<K, V, A> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert(key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+
+    fn range<R: RangeBounds<K>>(&self, range: R) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(self.range(range))
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K: Ord, V> AvlMap<K, V> {
+    /// Returns the mutable reference to the value.
+    ///
+    /// It's only available for the map without the [`Augment`] summary,
+    /// as the summary can't follow the changes through the reference.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut tree = &mut self.root;
+        loop {
+            let node = tree.0.as_mut()?;
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => tree = &mut node.left,
+                Ordering::Greater => tree = &mut node.right,
+                Ordering::Equal => return Some(&mut node.value),
+            }
+        }
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> AvlMap<K, V, A> {
+    /// Inserts the key/value and returns the old value in case
+    /// the key was already in the map.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.root.insert(key, value)
+    }
+
+    /// Removes the key and returns the value in case it was in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.root.remove(key).map(|(_, value)| value)
+    }
+
+    /// Returns the summary of the entries within the range in O(log n).
+    pub fn aggregate<Q, R>(&self, range: R) -> Option<A>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.root.aggregate(&range, false, false)
+    }
+
+    /// Builds the perfectly balanced map in O(n) from the entries sorted
+    /// by the key.
+    ///
+    /// # Panics
+    ///
+    /// It panics when the keys are not strictly ascending.
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let entries: Vec<_> = iter.into_iter().collect();
+        assert!(
+            entries.windows(2).all(|pair| pair[0].0 < pair[1].0),
+            "unsorted keys"
+        );
+        let len = entries.len();
+        Self {
+            root: Tree::build(&mut entries.into_iter(), len),
+        }
+    }
+
+    /// Splits the map into the entries less than the key, the entry of
+    /// the key, and the entries greater than the key in O(log n).
+    pub fn split<Q>(self, key: &Q) -> (Self, Option<(K, V)>, Self)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (left, entry, right) = self.root.split(key);
+        (Self { root: left }, entry, Self { root: right })
+    }
+
+    /// Moves the entries greater than or equal to the key to the returned
+    /// map in O(log n).
+    pub fn split_off<Q>(&mut self, key: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (left, entry, right) = mem::take(&mut self.root).split(key);
+        self.root = left;
+        let right = match entry {
+            Some((key, value)) => Tree::join(Tree::new(), key, value, right),
+            None => right,
+        };
+        Self { root: right }
+    }
+
+    /// Concatenates the map of which keys are all greater than the keys
+    /// of this map in O(log n).
+    ///
+    /// # Panics
+    ///
+    /// It panics when the keys overlap.
+    pub fn join(self, other: Self) -> Self {
+        if let (Some((last, _)), Some((first, _))) = (self.last(), other.first()) {
+            assert!(last < first, "overlapping keys");
+        }
+        Self {
+            root: Tree::concat(self.root, other.root),
+        }
+    }
+
+    /// Moves all the entries of `other` to this map, overwriting the
+    /// values of the same keys like `BTreeMap::append`.
+    pub fn append(&mut self, other: &mut Self) {
+        let root = Tree::union(mem::take(&mut self.root), mem::take(&mut other.root));
+        self.root = root;
+    }
+
+    /// Returns the map with the entries of both maps, taking the values
+    /// of `other` for the same keys, in O(m log(n/m + 1)) for the smaller
+    /// map size `m`.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            root: Tree::union(self.root, other.root),
+        }
+    }
+
+    /// Returns the entries of which keys are also in `other`.
+    pub fn intersection(self, other: Self) -> Self {
+        Self {
+            root: Tree::intersection(self.root, other.root),
+        }
+    }
+
+    /// Returns the entries of which keys are not in `other`.
+    pub fn difference(self, other: Self) -> Self {
+        Self {
+            root: Tree::difference(self.root, other.root),
+        }
+    }
+}
+
+impl<K: Ord + Debug, V, A: Augment<K, V> + PartialEq + Debug> AvlMap<K, V, A> {
+    /// Validates the tree, e.g. the key order, the heights, the balance
+    /// factors, the subtree sizes and the summaries.
+    ///
+    /// It walks the whole tree in O(n) and is meant for the debug builds
+    /// and the tests.
+    ///
+    /// # Panics
+    ///
+    /// It panics with the broken invariant.
+    pub fn check_invariants(&self) {
+        self.root.check_invariants(None, None);
+    }
+}
+
+impl<K: Ord, V, A> AvlMap<K, V, A> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut tree = &self.root;
+        while let Some(ref node) = tree.0 {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => tree = &node.left,
+                Ordering::Greater => tree = &node.right,
+                Ordering::Equal => return Some(&node.value),
+            }
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the number of keys less than `key` in O(log n).
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut rank = 0;
+        let mut tree = &self.root;
+        while let Some(ref node) = tree.0 {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => tree = &node.left,
+                Ordering::Greater => {
+                    rank += node.left.size() + 1;
+                    tree = &node.right;
+                }
+                Ordering::Equal => return rank + node.left.size(),
+            }
+        }
+        rank
+    }
+
+    /// Returns the entry with the greatest key less than or equal to `key`.
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut found = None;
+        let mut tree = &self.root;
+        while let Some(ref node) = tree.0 {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => tree = &node.left,
+                Ordering::Greater => {
+                    found = Some(node);
+                    tree = &node.right;
+                }
+                Ordering::Equal => return Some((&node.key, &node.value)),
+            }
+        }
+        found.map(|node| (&node.key, &node.value))
+    }
+
+    /// Returns the entry with the least key greater than or equal to `key`.
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut found = None;
+        let mut tree = &self.root;
+        while let Some(ref node) = tree.0 {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => {
+                    found = Some(node);
+                    tree = &node.left;
+                }
+                Ordering::Greater => tree = &node.right,
+                Ordering::Equal => return Some((&node.key, &node.value)),
+            }
+        }
+        found.map(|node| (&node.key, &node.value))
+    }
+
+    /// Returns the in-order iterator over the entries within the range.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V, A>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        // the entries from the start bound.
+        let mut stack = Vec::new();
+        let mut tree = &self.root;
+        while let Some(ref node) = tree.0 {
+            if is_after(range.start_bound(), node.key.borrow()) {
+                stack.push(&**node);
+                tree = &node.left;
+            } else {
+                tree = &node.right;
+            }
+        }
+
+        // the last entry before the end bound.
+        let mut last = None;
+        let mut tree = &self.root;
+        while let Some(ref node) = tree.0 {
+            if is_before(range.end_bound(), node.key.borrow()) {
+                last = Some(&**node);
+                tree = &node.right;
+            } else {
+                tree = &node.left;
+            }
+        }
+
+        match (stack.last(), last) {
+            (Some(first), Some(last)) if first.key <= last.key => Range {
+                stack,
+                last: Some(last),
+            },
+            _ => Range {
+                stack: Vec::new(),
+                last: None,
+            },
+        }
+    }
+}
+
+impl<K, V> AvlMap<K, V> {
+    pub const fn new() -> Self {
+        Self { root: Tree::new() }
+    }
+}
+
+impl<K, V, A> AvlMap<K, V, A> {
+    /// Creates a map keeping the [`Augment`] summary `A` in each node.
+    pub const fn augmented() -> Self {
+        Self { root: Tree::new() }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.root.0.is_none()
+    }
+
+    pub const fn len(&self) -> usize {
+        self.root.size()
+    }
+
+    /// Returns the height of the tree, which is zero for the empty tree.
+    pub const fn height(&self) -> usize {
+        self.root.height()
+    }
+
+    /// Renders the tree in the Graphviz DOT language.
+    pub fn to_dot(&self) -> String
+    where
+        K: Debug,
+        V: Debug,
+    {
+        render::dot(self.root.0.as_deref())
+    }
+
+    /// Renders the tree top-down with the box-drawing characters.
+    pub fn to_boxes(&self) -> String
+    where
+        K: Debug,
+        V: Debug,
+    {
+        render::boxes(self.root.0.as_deref())
+    }
+
+    /// Returns the in-order iterator over the entries.
+    pub fn iter(&self) -> Iter<'_, K, V, A> {
+        let mut stack = Vec::new();
+        push_left(&mut stack, &self.root);
+        Iter {
+            stack,
+            len: self.len(),
+        }
+    }
+
+    /// Returns the `k`th smallest entry, counting from zero, in O(log n).
+    pub fn select(&self, mut k: usize) -> Option<(&K, &V)> {
+        let mut tree = &self.root;
+        while let Some(ref node) = tree.0 {
+            let left = node.left.size();
+            match k.cmp(&left) {
+                Ordering::Less => tree = &node.left,
+                Ordering::Greater => {
+                    k -= left + 1;
+                    tree = &node.right;
+                }
+                Ordering::Equal => return Some((&node.key, &node.value)),
+            }
+        }
+        None
+    }
+
+    /// Returns the entry with the smallest key.
+    pub fn first(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.0.as_ref()?;
+        while let Some(ref left) = node.left.0 {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    /// Returns the entry with the largest key.
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.0.as_ref()?;
+        while let Some(ref right) = node.right.0 {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+}
+
+/// An in-order iterator over the entries of [`AvlMap`].
+#[derive(Debug)]
+pub struct Iter<'a, K, V, A = ()> {
+    stack: Vec<&'a Node<K, V, A>>,
+    len: usize,
+}
+
+impl<'a, K, V, A> Iterator for Iter<'a, K, V, A> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        push_left(&mut self.stack, &node.right);
+        self.len -= 1;
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V, A> ExactSizeIterator for Iter<'_, K, V, A> {}
+
+/// An in-order iterator over the entries of [`AvlMap`] within a range.
+#[derive(Debug)]
+pub struct Range<'a, K, V, A = ()> {
+    stack: Vec<&'a Node<K, V, A>>,
+    last: Option<&'a Node<K, V, A>>,
+}
+
+impl<'a, K, V, A> Iterator for Range<'a, K, V, A> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = self.last?;
+        let node = self.stack.pop()?;
+        if std::ptr::eq(node, last) {
+            self.last = None;
+        } else {
+            push_left(&mut self.stack, &node.right);
+        }
+        Some((&node.key, &node.value))
+    }
+}
+
+/// Pushes the left spine of the tree to the in-order traversal stack.
+fn push_left<'a, K, V, A>(stack: &mut Vec<&'a Node<K, V, A>>, mut tree: &'a Tree<K, V, A>) {
+    while let Some(ref node) = tree.0 {
+        stack.push(node);
+        tree = &node.left;
+    }
+}
+
+/// Returns `true` when the key is after the start bound.
+fn is_after<Q: Ord + ?Sized>(bound: Bound<&Q>, key: &Q) -> bool {
+    match bound {
+        Bound::Included(start) => key >= start,
+        Bound::Excluded(start) => key > start,
+        Bound::Unbounded => true,
+    }
+}
+
+/// Returns `true` when the key is before the end bound.
+fn is_before<Q: Ord + ?Sized>(bound: Bound<&Q>, key: &Q) -> bool {
+    match bound {
+        Bound::Included(end) => key <= end,
+        Bound::Excluded(end) => key < end,
+        Bound::Unbounded => true,
+    }
+}
+
+#[derive(Debug)]
+struct Tree<K, V, A>(Option<Box<Node<K, V, A>>>);
+
+impl<K, V, A> Default for Tree<K, V, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> Tree<K, V, A> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = match self.0 {
+            None => {
+                self.0 = Some(Box::new(Node::from((key, value))));
+                None
+            }
+            Some(ref mut node) => match key.cmp(&node.key) {
+                Ordering::Less => node.left.insert(key, value),
+                Ordering::Greater => node.right.insert(key, value),
+                Ordering::Equal => Some(mem::replace(&mut node.value, value)),
+            },
+        };
+        self.rebalance();
+        old
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let removed = match self.0 {
+            None => return None,
+            Some(ref mut node) => match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.remove(key),
+                Ordering::Greater => node.right.remove(key),
+                Ordering::Equal => self.remove_top(),
+            },
+        };
+        self.rebalance();
+        removed
+    }
+
+    /// Returns the summary of the entries within the range, where
+    /// `from_start` and `to_end` tell if the whole subtree is known to be
+    /// after the start bound and before the end bound respectively.
+    fn aggregate<Q, R>(&self, range: &R, from_start: bool, to_end: bool) -> Option<A>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let node = self.0.as_ref()?;
+        if from_start && to_end {
+            return Some(node.aug.clone());
+        }
+        if !from_start && !is_after(range.start_bound(), node.key.borrow()) {
+            return node.right.aggregate(range, from_start, to_end);
+        }
+        if !to_end && !is_before(range.end_bound(), node.key.borrow()) {
+            return node.left.aggregate(range, from_start, to_end);
+        }
+        let aug = A::from_entry(&node.key, &node.value);
+        let aug = match node.left.aggregate(range, from_start, true) {
+            Some(left) => left.combine(&aug),
+            None => aug,
+        };
+        match node.right.aggregate(range, true, to_end) {
+            Some(right) => Some(aug.combine(&right)),
+            None => Some(aug),
+        }
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> Tree<K, V, A> {
+    /// Builds the perfectly balanced tree from the first `len` entries.
+    fn build<I: Iterator<Item = (K, V)>>(entries: &mut I, len: usize) -> Self {
+        if len == 0 {
+            return Self::new();
+        }
+        let left = Self::build(entries, len / 2);
+        let mut node = match entries.next() {
+            Some(entry) => Box::new(Node::from(entry)),
+            None => return left,
+        };
+        node.left = left;
+        node.right = Self::build(entries, len - len / 2 - 1);
+        node.update();
+        Self(Some(node))
+    }
+
+    /// Joins the trees with the key in between, e.g. all the keys of
+    /// `left` are less than `key` and all the keys of `right` are greater
+    /// than `key`, in O(|height difference|).
+    fn join(left: Self, key: K, value: V, right: Self) -> Self {
+        let (lh, rh) = (left.height(), right.height());
+        let mut tree = if lh > rh + 1 {
+            // goes down the right spine of the taller tree.
+            let mut node = left.0.unwrap();
+            node.right = Self::join(mem::take(&mut node.right), key, value, right);
+            Self(Some(node))
+        } else if rh > lh + 1 {
+            let mut node = right.0.unwrap();
+            node.left = Self::join(left, key, value, mem::take(&mut node.left));
+            Self(Some(node))
+        } else {
+            let mut node = Box::new(Node::from((key, value)));
+            node.left = left;
+            node.right = right;
+            Self(Some(node))
+        };
+        tree.rebalance();
+        tree
+    }
+
+    /// Joins the trees without the key in between.
+    fn concat(left: Self, mut right: Self) -> Self {
+        match right.remove_min() {
+            Some((key, value)) => Self::join(left, key, value, right),
+            None => left,
+        }
+    }
+
+    fn split<Q>(self, key: &Q) -> (Self, Option<(K, V)>, Self)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let node = match self.0 {
+            Some(node) => *node,
+            None => return (Self::new(), None, Self::new()),
+        };
+        match key.cmp(node.key.borrow()) {
+            Ordering::Less => {
+                let (left, entry, right) = node.left.split(key);
+                (
+                    left,
+                    entry,
+                    Self::join(right, node.key, node.value, node.right),
+                )
+            }
+            Ordering::Greater => {
+                let (left, entry, right) = node.right.split(key);
+                (
+                    Self::join(node.left, node.key, node.value, left),
+                    entry,
+                    right,
+                )
+            }
+            Ordering::Equal => (node.left, Some((node.key, node.value)), node.right),
+        }
+    }
+
+    /// Returns the union, taking the values of `other` for the same keys.
+    fn union(self, other: Self) -> Self {
+        let node = match self.0 {
+            None => return other,
+            Some(_) if other.0.is_none() => return self,
+            Some(node) => *node,
+        };
+        let (left, entry, right) = other.split(&node.key);
+        let value = entry.map(|(_, value)| value).unwrap_or(node.value);
+        Self::join(
+            node.left.union(left),
+            node.key,
+            value,
+            node.right.union(right),
+        )
+    }
+
+    fn intersection(self, other: Self) -> Self {
+        let node = match self.0 {
+            Some(_) if other.0.is_none() => return Self::new(),
+            None => return Self::new(),
+            Some(node) => *node,
+        };
+        let (left, entry, right) = other.split(&node.key);
+        let left = node.left.intersection(left);
+        let right = node.right.intersection(right);
+        match entry {
+            Some(_) => Self::join(left, node.key, node.value, right),
+            None => Self::concat(left, right),
+        }
+    }
+
+    fn difference(self, other: Self) -> Self {
+        let other = match other.0 {
+            _ if self.0.is_none() => return Self::new(),
+            None => return self,
+            Some(other) => *other,
+        };
+        let (left, _, right) = self.split(&other.key);
+        Self::concat(left.difference(other.left), right.difference(other.right))
+    }
+}
+
+impl<K: Ord + Debug, V, A: Augment<K, V> + PartialEq + Debug> Tree<K, V, A> {
+    /// Checks the subtree of which keys are between `min` and `max`
+    /// and returns its height.
+    fn check_invariants(&self, min: Option<&K>, max: Option<&K>) -> usize {
+        let node = match self.0 {
+            Some(ref node) => node,
+            None => return 0,
+        };
+        if let Some(min) = min {
+            assert!(node.key > *min, "{:?} is not after {min:?}", node.key);
+        }
+        if let Some(max) = max {
+            assert!(node.key < *max, "{:?} is not before {max:?}", node.key);
+        }
+        let left = node.left.check_invariants(min, Some(&node.key));
+        let right = node.right.check_invariants(Some(&node.key), max);
+        assert_eq!(
+            node.height,
+            1 + std::cmp::max(left, right),
+            "wrong height at {:?}",
+            node.key
+        );
+        assert!(
+            (-1..=1).contains(&self.balance()),
+            "unbalanced at {:?}",
+            node.key
+        );
+        assert_eq!(
+            node.size,
+            1 + node.left.size() + node.right.size(),
+            "wrong size at {:?}",
+            node.key
+        );
+        let aug = A::from_entry(&node.key, &node.value);
+        let aug = match node.left.0 {
+            Some(ref left) => left.aug.combine(&aug),
+            None => aug,
+        };
+        let aug = match node.right.0 {
+            Some(ref right) => aug.combine(&right.aug),
+            None => aug,
+        };
+        assert_eq!(node.aug, aug, "wrong summary at {:?}", node.key);
+        node.height
+    }
+}
+
+impl<K, V, A: Augment<K, V>> Tree<K, V, A> {
+    /// Removes the top node and returns its key/value.
+    fn remove_top(&mut self) -> Option<(K, V)> {
+        let mut node = self.0.take()?;
+        match (node.left.0.take(), node.right.0.take()) {
+            (None, right) => self.0 = right,
+            (left, None) => self.0 = left,
+            (left, right) => {
+                // replaces the top with the next entry.
+                node.left = Tree(left);
+                node.right = Tree(right);
+                let (key, value) = node.right.remove_min().expect("non-empty right subtree");
+                let key = mem::replace(&mut node.key, key);
+                let value = mem::replace(&mut node.value, value);
+                self.0 = Some(node);
+                return Some((key, value));
+            }
+        }
+        Some((node.key, node.value))
+    }
+
+    fn remove_min(&mut self) -> Option<(K, V)> {
+        let node = self.0.as_mut()?;
+        let removed = match node.left.0 {
+            Some(_) => node.left.remove_min(),
+            None => self.remove_top(),
+        };
+        self.rebalance();
+        removed
+    }
+
+    fn rebalance(&mut self) {
+        let balance = self.balance();
+        let node = match self.0.as_mut() {
+            Some(node) => node,
+            None => return,
+        };
+        match balance {
+            n if n > 1 => {
+                // the right-left case needs the double rotation.
+                if node.right.balance() < 0 {
+                    node.right.rotate_right();
+                }
+                self.rotate_left();
+            }
+            n if n < -1 => {
+                // so as the left-right case.
+                if node.left.balance() > 0 {
+                    node.left.rotate_left();
+                }
+                self.rotate_right();
+            }
+            _ => self.update(),
+        }
+        #[cfg(debug_assertions)]
+        self.check_top();
+    }
+
+    fn update(&mut self) {
+        if let Some(ref mut node) = self.0.as_mut() {
+            node.update();
+        }
+    }
+
+    fn rotate_left(&mut self) {
+        self.0 = self.0.take().map(|node| node.rotate_left());
+    }
+
+    fn rotate_right(&mut self) {
+        self.0 = self.0.take().map(|node| node.rotate_right());
+    }
+}
+
+impl<K, V, A> Tree<K, V, A> {
+    const fn new() -> Self {
+        Self(None)
+    }
+
+    const fn balance(&self) -> isize {
+        match self.0 {
+            Some(ref node) => node.right.height() as isize - node.left.height() as isize,
+            None => 0,
+        }
+    }
+
+    const fn height(&self) -> usize {
+        match self.0 {
+            Some(ref node) => node.height,
+            None => 0,
+        }
+    }
+
+    const fn size(&self) -> usize {
+        match self.0 {
+            Some(ref node) => node.size,
+            None => 0,
+        }
+    }
+
+    /// Checks the balance factor, the height and the size of the top node
+    /// against its children.
+    ///
+    /// It runs after each rebalance in the debug builds, e.g. on every
+    /// node on the path of each insert and remove, so that the broken
+    /// invariant panics right where it's broken without walking the whole
+    /// tree as [`AvlMap::check_invariants`] does.
+    #[cfg(debug_assertions)]
+    fn check_top(&self) {
+        if let Some(ref node) = self.0 {
+            assert!((-1..=1).contains(&self.balance()), "unbalanced");
+            assert_eq!(
+                node.height,
+                1 + std::cmp::max(node.left.height(), node.right.height()),
+                "wrong height"
+            );
+            assert_eq!(
+                node.size,
+                1 + node.left.size() + node.right.size(),
+                "wrong size"
+            );
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, A> Tree<K, V, A> {
+    fn print_depth_first(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        if let Some(ref node) = self.0 {
+            node.left.print_depth_first(f, depth + 1)?;
+            writeln!(
+                f,
+                "{}:{:.<depth$}{:?}={:?}",
+                node.height, "", node.key, node.value
+            )?;
+            node.right.print_depth_first(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Node<K, V, A> {
+    key: K,
+    value: V,
+    height: usize,
+    /// The number of entries in the subtree.
+    size: usize,
+    aug: A,
+    left: Tree<K, V, A>,
+    right: Tree<K, V, A>,
+}
+
+impl<K, V, A: Augment<K, V>> From<(K, V)> for Node<K, V, A> {
+    fn from((key, value): (K, V)) -> Self {
+        Self {
+            aug: A::from_entry(&key, &value),
+            key,
+            value,
+            height: 1,
+            size: 1,
+            left: Tree::default(),
+            right: Tree::default(),
+        }
+    }
+}
+
+impl<K, V, A: Augment<K, V>> Node<K, V, A> {
+    fn rotate_left(mut self) -> Box<Self> {
+        // picks the right node as the new top node.
+        let mut top = match self.right.0.take() {
+            Some(right) => right,
+            None => return Box::new(self),
+        };
+        self.right = Tree(top.left.0.take());
+        self.right.update();
+        top.left = Tree(Some(Box::new(self)));
+        top.left.update();
+        top.update();
+        top
+    }
+
+    fn rotate_right(mut self) -> Box<Self> {
+        let mut top = match self.left.0.take() {
+            Some(node) => node,
+            None => return Box::new(self),
+        };
+        self.left = Tree(top.right.0.take());
+        self.left.update();
+        top.right = Tree(Some(Box::new(self)));
+        top.right.update();
+        top.update();
+        top
+    }
+
+    /// Updates the height, the size and the summary from the children.
+    fn update(&mut self) {
+        self.height = 1 + std::cmp::max(self.left.height(), self.right.height());
+        self.size = 1 + self.left.size() + self.right.size();
+        let aug = A::from_entry(&self.key, &self.value);
+        let aug = match self.left.0 {
+            Some(ref left) => left.aug.combine(&aug),
+            None => aug,
+        };
+        self.aug = match self.right.0 {
+            Some(ref right) => aug.combine(&right.aug),
+            None => aug,
+        };
+    }
+}
+
+impl<K: Debug, V: Debug, A> Render for Node<K, V, A> {
+    fn label(&self) -> String {
+        format!("{:?}={:?}", self.key, self.value)
+    }
+
+    fn info(&self) -> Option<String> {
+        Some(format!("h={}", self.height))
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.0.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.0.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod test;
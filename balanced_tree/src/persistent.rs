@@ -0,0 +1,424 @@
+//! A persistent AVL tree
+
+use std::borrow::Borrow;
+use std::cmp::{self, Ordering};
+use std::sync::Arc;
+
+/// A persistent ordered map based on the AVL tree.
+///
+/// The nodes are shared through [`Arc`] among the versions, e.g. `clone`
+/// is O(1) and [`insert`](Self::insert) and [`remove`](Self::remove)
+/// return the new version in O(log n) by copying only the path to the key.
+#[derive(Debug)]
+pub struct PersistentMap<K, V> {
+    root: Link<K, V>,
+}
+
+type Link<K, V> = Option<Arc<Node<K, V>>>;
+
+impl<K, V> Clone for PersistentMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for PersistentMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a PersistentMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> FromIterator<(K, V)> for PersistentMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |map, (key, value)| map.insert(key, value))
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> PersistentMap<K, V> {
+    /// Returns the new version with the key/value.
+    pub fn insert(&self, key: K, value: V) -> Self {
+        Self {
+            root: Some(insert(&self.root, key, value)),
+        }
+    }
+
+    /// Returns the new version without the key.
+    ///
+    /// It shares the whole tree with this version in case the key
+    /// is not in the map.
+    pub fn remove<Q>(&self, key: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match remove(&self.root, key) {
+            Some(root) => Self { root },
+            None => self.clone(),
+        }
+    }
+}
+
+impl<K: Ord, V> PersistentMap<K, V> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut link = &self.root;
+        while let Some(ref node) = link {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => link = &node.left,
+                Ordering::Greater => link = &node.right,
+                Ordering::Equal => return Some(&node.value),
+            }
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the changes from this version to the `other` version
+    /// in the key order.
+    ///
+    /// It skips the subtrees shared by the versions, e.g. it takes
+    /// O(d log n) for the `d` changes between the close versions.
+    pub fn diff<'a>(&'a self, other: &'a Self) -> Diff<'a, K, V>
+    where
+        V: PartialEq,
+    {
+        Diff {
+            old: self.root.iter().map(Item::Tree).collect(),
+            new: other.root.iter().map(Item::Tree).collect(),
+        }
+    }
+}
+
+impl<K, V> PersistentMap<K, V> {
+    pub const fn new() -> Self {
+        Self { root: None }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    /// Returns `true` when both versions share the same tree.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the in-order iterator over the entries.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut stack = Vec::new();
+        push_left(&mut stack, &self.root);
+        Iter {
+            stack,
+            len: self.len(),
+        }
+    }
+}
+
+/// An in-order iterator over the entries of [`PersistentMap`].
+#[derive(Debug)]
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        push_left(&mut self.stack, &node.right);
+        self.len -= 1;
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// A change between the versions of [`PersistentMap`].
+#[derive(Debug, PartialEq)]
+pub enum Change<'a, K, V> {
+    Added(&'a K, &'a V),
+    Removed(&'a K, &'a V),
+    /// The key with the old and the new values.
+    Changed(&'a K, &'a V, &'a V),
+}
+
+/// An iterator over the changes between the versions of [`PersistentMap`].
+#[derive(Debug)]
+pub struct Diff<'a, K, V> {
+    // the in-order traversal stacks of the both versions, of which top
+    // is the subtree or the entry with the next smallest keys.
+    old: Vec<Item<'a, K, V>>,
+    new: Vec<Item<'a, K, V>>,
+}
+
+#[derive(Debug)]
+enum Item<'a, K, V> {
+    /// The subtree not expanded yet.
+    Tree(&'a Arc<Node<K, V>>),
+    Entry(&'a Node<K, V>),
+}
+
+impl<K, V> Clone for Item<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Item<'_, K, V> {}
+
+impl<'a, K: Ord, V: PartialEq> Iterator for Diff<'a, K, V> {
+    type Item = Change<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match (self.old.last().copied(), self.new.last().copied()) {
+                (None, None) => return None,
+                (Some(Item::Tree(a)), Some(Item::Tree(b))) => {
+                    if Arc::ptr_eq(a, b) {
+                        // both versions continue with the same entries.
+                        self.old.pop();
+                        self.new.pop();
+                    } else if a.height >= b.height {
+                        expand(&mut self.old);
+                    } else {
+                        expand(&mut self.new);
+                    }
+                }
+                (Some(Item::Tree(_)), _) => expand(&mut self.old),
+                (_, Some(Item::Tree(_))) => expand(&mut self.new),
+                (Some(Item::Entry(a)), Some(Item::Entry(b))) => match a.key.cmp(&b.key) {
+                    Ordering::Less => {
+                        self.old.pop();
+                        return Some(Change::Removed(&a.key, &a.value));
+                    }
+                    Ordering::Greater => {
+                        self.new.pop();
+                        return Some(Change::Added(&b.key, &b.value));
+                    }
+                    Ordering::Equal => {
+                        self.old.pop();
+                        self.new.pop();
+                        if !std::ptr::eq(a, b) && a.value != b.value {
+                            return Some(Change::Changed(&a.key, &a.value, &b.value));
+                        }
+                    }
+                },
+                (Some(Item::Entry(a)), None) => {
+                    self.old.pop();
+                    return Some(Change::Removed(&a.key, &a.value));
+                }
+                (None, Some(Item::Entry(b))) => {
+                    self.new.pop();
+                    return Some(Change::Added(&b.key, &b.value));
+                }
+            }
+        }
+    }
+}
+
+/// Replaces the subtree on top of the stack with its left subtree,
+/// the top entry and its right subtree.
+fn expand<K, V>(stack: &mut Vec<Item<'_, K, V>>) {
+    if let Some(Item::Tree(node)) = stack.pop() {
+        stack.extend(node.right.iter().map(Item::Tree));
+        stack.push(Item::Entry(node));
+        stack.extend(node.left.iter().map(Item::Tree));
+    }
+}
+
+/// Pushes the left spine of the tree to the in-order traversal stack.
+fn push_left<'a, K, V>(stack: &mut Vec<&'a Node<K, V>>, mut link: &'a Link<K, V>) {
+    while let Some(ref node) = link {
+        stack.push(node);
+        link = &node.left;
+    }
+}
+
+#[derive(Debug)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    height: usize,
+    /// The number of entries in the subtree.
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+fn insert<K: Ord + Clone, V: Clone>(link: &Link<K, V>, key: K, value: V) -> Arc<Node<K, V>> {
+    let node = match link {
+        None => return new_node(key, value, None, None),
+        Some(node) => node,
+    };
+    match key.cmp(&node.key) {
+        Ordering::Less => balance(
+            node.key.clone(),
+            node.value.clone(),
+            Some(insert(&node.left, key, value)),
+            node.right.clone(),
+        ),
+        Ordering::Greater => balance(
+            node.key.clone(),
+            node.value.clone(),
+            node.left.clone(),
+            Some(insert(&node.right, key, value)),
+        ),
+        Ordering::Equal => new_node(key, value, node.left.clone(), node.right.clone()),
+    }
+}
+
+/// Returns the new tree without the key, or `None` in case the key
+/// is not in the tree.
+fn remove<K, V, Q>(link: &Link<K, V>, key: &Q) -> Option<Link<K, V>>
+where
+    K: Borrow<Q> + Clone,
+    V: Clone,
+    Q: Ord + ?Sized,
+{
+    let node = link.as_ref()?;
+    let node = match key.cmp(node.key.borrow()) {
+        Ordering::Less => balance(
+            node.key.clone(),
+            node.value.clone(),
+            remove(&node.left, key)?,
+            node.right.clone(),
+        ),
+        Ordering::Greater => balance(
+            node.key.clone(),
+            node.value.clone(),
+            node.left.clone(),
+            remove(&node.right, key)?,
+        ),
+        Ordering::Equal => match (&node.left, &node.right) {
+            (None, right) => return Some(right.clone()),
+            (left, None) => return Some(left.clone()),
+            (left, Some(right)) => {
+                // replaces the node with the next entry.
+                let (key, value, right) = remove_min(right);
+                balance(key, value, left.clone(), right)
+            }
+        },
+    };
+    Some(Some(node))
+}
+
+fn remove_min<K: Clone, V: Clone>(node: &Node<K, V>) -> (K, V, Link<K, V>) {
+    match node.left {
+        None => (node.key.clone(), node.value.clone(), node.right.clone()),
+        Some(ref left) => {
+            let (key, value, left) = remove_min(left);
+            let node = balance(
+                node.key.clone(),
+                node.value.clone(),
+                left,
+                node.right.clone(),
+            );
+            (key, value, Some(node))
+        }
+    }
+}
+
+/// Creates the node with the rotations in case the subtrees are
+/// out of balance.
+fn balance<K: Clone, V: Clone>(
+    key: K,
+    value: V,
+    left: Link<K, V>,
+    right: Link<K, V>,
+) -> Arc<Node<K, V>> {
+    let (lh, rh) = (height(&left), height(&right));
+    if lh > rh + 1 {
+        let l = left.as_ref().unwrap();
+        if height(&l.left) >= height(&l.right) {
+            let top = new_node(key, value, l.right.clone(), right);
+            new_node(l.key.clone(), l.value.clone(), l.left.clone(), Some(top))
+        } else {
+            // the left-right case.
+            let lr = l.right.as_ref().unwrap();
+            let left = new_node(
+                l.key.clone(),
+                l.value.clone(),
+                l.left.clone(),
+                lr.left.clone(),
+            );
+            let right = new_node(key, value, lr.right.clone(), right);
+            new_node(lr.key.clone(), lr.value.clone(), Some(left), Some(right))
+        }
+    } else if rh > lh + 1 {
+        let r = right.as_ref().unwrap();
+        if height(&r.right) >= height(&r.left) {
+            let top = new_node(key, value, left, r.left.clone());
+            new_node(r.key.clone(), r.value.clone(), Some(top), r.right.clone())
+        } else {
+            // the right-left case.
+            let rl = r.left.as_ref().unwrap();
+            let left = new_node(key, value, left, rl.left.clone());
+            let right = new_node(
+                r.key.clone(),
+                r.value.clone(),
+                rl.right.clone(),
+                r.right.clone(),
+            );
+            new_node(rl.key.clone(), rl.value.clone(), Some(left), Some(right))
+        }
+    } else {
+        new_node(key, value, left, right)
+    }
+}
+
+fn new_node<K, V>(key: K, value: V, left: Link<K, V>, right: Link<K, V>) -> Arc<Node<K, V>> {
+    Arc::new(Node {
+        key,
+        value,
+        height: 1 + cmp::max(height(&left), height(&right)),
+        size: 1 + size(&left) + size(&right),
+        left,
+        right,
+    })
+}
+
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map(|node| node.height).unwrap_or(0)
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map(|node| node.size).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test;
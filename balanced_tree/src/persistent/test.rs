@@ -0,0 +1,151 @@
+use super::{Change, Link, PersistentMap};
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Checks the key order, the heights, the balance and the sizes,
+/// and returns the height.
+fn check<K: Ord, V>(link: &Link<K, V>, min: Option<&K>, max: Option<&K>) -> usize {
+    let node = match link {
+        Some(node) => node,
+        None => return 0,
+    };
+    assert!(min.map(|min| node.key > *min).unwrap_or(true));
+    assert!(max.map(|max| node.key < *max).unwrap_or(true));
+    let left = check(&node.left, min, Some(&node.key));
+    let right = check(&node.right, Some(&node.key), max);
+    assert!(left.abs_diff(right) <= 1);
+    assert_eq!(node.height, 1 + left.max(right));
+    assert_eq!(
+        node.size,
+        1 + super::size(&node.left) + super::size(&node.right)
+    );
+    node.height
+}
+
+/// Returns the changes between the maps by scanning both of them.
+fn brute_force_diff<'a>(
+    old: &'a BTreeMap<i32, i32>,
+    new: &'a BTreeMap<i32, i32>,
+) -> Vec<Change<'a, i32, i32>> {
+    let mut keys: Vec<_> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter_map(|k| match (old.get_key_value(k), new.get_key_value(k)) {
+            (Some((k, a)), Some((_, b))) if a != b => Some(Change::Changed(k, a, b)),
+            (Some((k, a)), None) => Some(Change::Removed(k, a)),
+            (None, Some((k, b))) => Some(Change::Added(k, b)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_insert_keeps_old_versions() {
+    let empty = PersistentMap::new();
+    let one = empty.insert(1, "one");
+    let two = one.insert(2, "two");
+    let three = two.insert(1, "uno");
+
+    assert!(empty.is_empty());
+    assert_eq!(one.len(), 1);
+    assert_eq!(two.len(), 2);
+    assert_eq!(three.len(), 2);
+    assert_eq!(one.get(&1), Some(&"one"));
+    assert_eq!(one.get(&2), None);
+    assert_eq!(two.get(&1), Some(&"one"));
+    assert_eq!(three.get(&1), Some(&"uno"));
+    assert!(three.contains_key(&2));
+}
+
+#[test]
+fn test_remove() {
+    let map: PersistentMap<_, _> = (0..100).map(|k| (k, k * 10)).collect();
+    let removed = map.remove(&50);
+    assert_eq!(removed.len(), 99);
+    assert_eq!(removed.get(&50), None);
+    assert_eq!(map.get(&50), Some(&500));
+    check(&removed.root, None, None);
+
+    // nothing to copy without the key.
+    assert!(map.remove(&100).ptr_eq(&map));
+    assert!(map.clone().ptr_eq(&map));
+    assert!(!removed.ptr_eq(&map));
+}
+
+#[test]
+fn test_structural_sharing() {
+    let map: PersistentMap<_, _> = (0..1_024).map(|k| (k, k)).collect();
+    let new = map.insert(1_000, 0);
+    // only the path to the key is copied.
+    let (old_root, new_root) = (map.root.as_ref().unwrap(), new.root.as_ref().unwrap());
+    assert!(Arc::ptr_eq(
+        old_root.left.as_ref().unwrap(),
+        new_root.left.as_ref().unwrap()
+    ));
+    assert!(!Arc::ptr_eq(
+        old_root.right.as_ref().unwrap(),
+        new_root.right.as_ref().unwrap()
+    ));
+    assert_eq!(map.get(&1_000), Some(&1_000));
+}
+
+#[test]
+fn test_diff() {
+    let old: PersistentMap<_, _> = (0..10).map(|k| (k, k)).collect();
+    let new = old.insert(3, 30).remove(&5).insert(11, 11).insert(7, 7);
+    let changes: Vec<_> = old.diff(&new).collect();
+    assert_eq!(
+        changes,
+        vec![
+            Change::Changed(&3, &3, &30),
+            Change::Removed(&5, &5),
+            Change::Added(&11, &11),
+        ]
+    );
+    assert_eq!(old.diff(&old).count(), 0);
+    assert_eq!(PersistentMap::new().diff(&old).count(), 10);
+}
+
+#[test]
+fn test_against_btree_map() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut versions = vec![(PersistentMap::new(), BTreeMap::new())];
+
+    for _ in 0..2_000 {
+        // branches off from any of the versions.
+        let (map, oracle) = &versions[rng.gen_range(0..versions.len())];
+        let (mut map, mut oracle) = (map.clone(), oracle.clone());
+        for _ in 0..rng.gen_range(1..8) {
+            let k = rng.gen_range(0..300);
+            if rng.gen_bool(0.6) {
+                map = map.insert(k, rng.gen_range(0..3));
+                oracle.insert(k, *map.get(&k).unwrap());
+            } else {
+                map = map.remove(&k);
+                oracle.remove(&k);
+            }
+        }
+        check(&map.root, None, None);
+        assert_eq!(map.len(), oracle.len());
+        assert!(map.iter().eq(oracle.iter()));
+        versions.push((map, oracle));
+    }
+
+    // all the versions are still valid.
+    for (map, oracle) in &versions {
+        assert!(map.iter().eq(oracle.iter()));
+    }
+    for _ in 0..500 {
+        let (a, a_oracle) = &versions[rng.gen_range(0..versions.len())];
+        let (b, b_oracle) = &versions[rng.gen_range(0..versions.len())];
+        assert_eq!(
+            a.diff(b).collect::<Vec<_>>(),
+            brute_force_diff(a_oracle, b_oracle)
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use super::{Augment, AvlMap};
+
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn map_from(keys: &[i32]) -> AvlMap<i32, i32> {
+    let mut map = AvlMap::new();
+    for k in keys {
+        map.insert(*k, k * 10);
+    }
+    map
+}
+
+#[test]
+fn test_insert_get() {
+    let mut map = map_from(&[4, 5, 6, 10, 1, 94, 54, 3]);
+    assert_eq!(map.len(), 8);
+    assert_eq!(map.get(&10), Some(&100));
+    assert_eq!(map.get(&11), None);
+    assert!(map.contains_key(&94));
+    assert!(!map.contains_key(&95));
+
+    assert_eq!(map.insert(10, 1), Some(100));
+    assert_eq!(map.len(), 8);
+    *map.get_mut(&10).unwrap() += 1;
+    assert_eq!(map.get(&10), Some(&2));
+}
+
+#[test]
+fn test_borrowed_key() {
+    let mut map = AvlMap::new();
+    map.insert("keith".to_string(), 18);
+    map.insert("dave".to_string(), 1);
+    assert_eq!(map.get("keith"), Some(&18));
+    assert_eq!(map.remove("dave"), Some(1));
+    assert!(!map.contains_key("dave"));
+}
+
+#[test]
+fn test_remove() {
+    let mut map = map_from(&[4, 5, 6, 10, 1, 94, 54, 3]);
+    assert_eq!(map.remove(&5), Some(50));
+    assert_eq!(map.remove(&5), None);
+    assert_eq!(map.remove(&4), Some(40));
+    assert_eq!(map.remove(&94), Some(940));
+    assert_eq!(map.len(), 5);
+    let keys: Vec<_> = map.iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec![1, 3, 6, 10, 54]);
+}
+
+#[test]
+fn test_iter() {
+    let map = map_from(&[4, 5, 6, 10, 1, 94, 54, 3]);
+    let keys: Vec<_> = map.iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec![1, 3, 4, 5, 6, 10, 54, 94]);
+    assert_eq!(map.iter().len(), 8);
+    assert_eq!((&map).into_iter().count(), 8);
+    assert_eq!(AvlMap::<i32, i32>::new().iter().next(), None);
+}
+
+#[test]
+fn test_first_last() {
+    let map = map_from(&[4, 5, 6, 10, 1, 94, 54, 3]);
+    assert_eq!(map.first(), Some((&1, &10)));
+    assert_eq!(map.last(), Some((&94, &940)));
+    let map = AvlMap::<i32, i32>::new();
+    assert_eq!(map.first(), None);
+    assert_eq!(map.last(), None);
+}
+
+#[test]
+fn test_floor_ceiling() {
+    let map = map_from(&[4, 5, 6, 10, 1, 94, 54, 3]);
+    assert_eq!(map.floor(&50), Some((&10, &100)));
+    assert_eq!(map.floor(&54), Some((&54, &540)));
+    assert_eq!(map.floor(&0), None);
+    assert_eq!(map.ceiling(&50), Some((&54, &540)));
+    assert_eq!(map.ceiling(&54), Some((&54, &540)));
+    assert_eq!(map.ceiling(&95), None);
+}
+
+#[test]
+fn test_range() {
+    let map = map_from(&[4, 5, 6, 10, 1, 94, 54, 3]);
+    let keys =
+        |r: (Bound<i32>, Bound<i32>)| -> Vec<i32> { map.range(r).map(|(k, _)| *k).collect() };
+    assert_eq!(map.range(3..10).count(), 4);
+    assert_eq!(map.range(..).count(), 8);
+    assert_eq!(map.range(11..54).count(), 0);
+    assert_eq!(
+        map.range(50..=54).map(|(k, _)| *k).collect::<Vec<_>>(),
+        vec![54]
+    );
+    assert_eq!(
+        keys((Bound::Excluded(4), Bound::Excluded(54))),
+        vec![5, 6, 10]
+    );
+    assert_eq!(keys((Bound::Excluded(94), Bound::Unbounded)), vec![]);
+    assert_eq!(keys((Bound::Included(10), Bound::Excluded(5))), vec![]);
+}
+
+#[test]
+fn test_against_btree_map() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut map = AvlMap::new();
+    let mut oracle = BTreeMap::new();
+
+    for _ in 0..10_000 {
+        let k = rng.gen_range(0..500);
+        match rng.gen_range(0..4) {
+            0 | 1 => assert_eq!(map.insert(k, k * 10), oracle.insert(k, k * 10)),
+            2 => assert_eq!(map.remove(&k), oracle.remove(&k)),
+            _ => {
+                assert_eq!(map.get(&k), oracle.get(&k));
+                assert_eq!(map.floor(&k), oracle.range(..=k).next_back());
+                assert_eq!(map.ceiling(&k), oracle.range(k..).next());
+                let end = k + rng.gen_range(0..50);
+                assert!(map.range(k..end).eq(oracle.range(k..end)));
+            }
+        }
+        assert_eq!(map.len(), oracle.len());
+    }
+    map.check_invariants();
+    assert!(map.iter().eq(oracle.iter()));
+    assert_eq!(map.first(), oracle.iter().next());
+    assert_eq!(map.last(), oracle.iter().next_back());
+}
+
+#[test]
+fn test_double_rotations() {
+    // left-right, right-left and the mixed cases.
+    for keys in [[3, 1, 2], [1, 3, 2], [2, 1, 3]] {
+        let map = map_from(&keys);
+        map.check_invariants();
+        assert_eq!(map.height(), 2);
+        assert_eq!(map.root.0.as_ref().map(|node| node.key), Some(2));
+    }
+    let map = map_from(&[50, 20, 80, 10, 30, 25]);
+    map.check_invariants();
+    assert_eq!(map.root.0.as_ref().map(|node| node.key), Some(30));
+}
+
+#[test]
+fn test_height() {
+    let mut map = AvlMap::new();
+    assert_eq!(map.height(), 0);
+    for k in 0..100_000 {
+        map.insert(k, ());
+    }
+    map.check_invariants();
+    // the AVL tree is at most 1.44 log2(n + 2) high.
+    let max = 1.44 * ((map.len() + 2) as f64).log2();
+    assert!((map.height() as f64) <= max, "{} > {max}", map.height());
+}
+
+/// The inserts (`true`) and removes of the keys below the random bound,
+/// e.g. the small bound gives many updates of the same keys.
+fn ops() -> impl Strategy<Value = Vec<(bool, i32)>> {
+    (1..1_000).prop_flat_map(|max| vec((prop::bool::weighted(0.6), 0..max), 0..2_000))
+}
+
+proptest! {
+    #[test]
+    fn test_random_invariants(ops in ops()) {
+        let mut map = AvlMap::<i32, i32, Sum>::augmented();
+        let mut oracle = BTreeMap::new();
+        for (insert, k) in ops {
+            if insert {
+                prop_assert_eq!(map.insert(k, k), oracle.insert(k, k));
+            } else {
+                prop_assert_eq!(map.remove(&k), oracle.remove(&k));
+            }
+        }
+        map.check_invariants();
+        prop_assert!(map.iter().eq(oracle.iter()));
+    }
+
+    #[test]
+    fn test_random_split_join(
+        a in vec(0..1_000, 0..500),
+        b in vec(0..1_000, 0..500),
+        at in 0..1_000,
+    ) {
+        let (mut map, mut other) = (AvlMap::<i32, i32, Sum>::augmented(), AvlMap::augmented());
+        for &k in &a {
+            map.insert(k, k);
+        }
+        for &k in &b {
+            other.insert(k, k);
+        }
+        let (left, entry, right) = map.split(&at);
+        left.check_invariants();
+        right.check_invariants();
+        prop_assert_eq!(entry.is_some(), a.contains(&at));
+        prop_assert!(left.iter().all(|(k, _)| *k < at));
+        prop_assert!(right.iter().all(|(k, _)| *k > at));
+
+        // the union of all but the split key.
+        let union = left.join(right).union(other);
+        union.check_invariants();
+        let mut want: Vec<_> = a.into_iter().filter(|&k| k != at).chain(b).collect();
+        want.sort_unstable();
+        want.dedup();
+        prop_assert!(union.iter().map(|(k, _)| *k).eq(want));
+    }
+}
+
+#[test]
+#[should_panic(expected = "unbalanced")]
+fn test_check_invariants() {
+    use super::{Node, Tree};
+
+    // chains the keys to the right without rebalancing.
+    let mut map = map_from(&[1]);
+    let mut tree = Tree::new();
+    for k in [3, 2] {
+        let mut node = Node::from((k, k * 10));
+        node.right = tree;
+        node.update();
+        tree = Tree(Some(Box::new(node)));
+    }
+    map.root.0.as_mut().unwrap().right = tree;
+    map.root.update();
+    map.check_invariants();
+}
+
+#[test]
+fn test_render() {
+    let map = map_from(&[2, 1, 3]);
+    assert_eq!(
+        map.to_boxes(),
+        "     ┌─────2=20 [h=2]──────┐
+1=10 [h=1]            3=30 [h=1]
+"
+    );
+    assert_eq!(
+        map.to_dot(),
+        "digraph {
+    n0 [label=\"2=20\\nh=2\"];
+    n1 [label=\"1=10\\nh=1\"];
+    n0:sw -> n1;
+    n2 [label=\"3=30\\nh=1\"];
+    n0:se -> n2;
+}
+"
+    );
+}
+
+#[test]
+fn test_select_rank() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut map = AvlMap::new();
+    let mut oracle = BTreeMap::new();
+
+    for _ in 0..5_000 {
+        let k = rng.gen_range(0..500);
+        if rng.gen_bool(0.7) {
+            map.insert(k, k);
+            oracle.insert(k, k);
+        } else {
+            map.remove(&k);
+            oracle.remove(&k);
+        }
+        assert_eq!(map.rank(&k), oracle.range(..k).count());
+    }
+    for (i, entry) in oracle.iter().enumerate() {
+        assert_eq!(map.select(i), Some(entry));
+        assert_eq!(map.rank(entry.0), i);
+    }
+    assert_eq!(map.select(oracle.len()), None);
+    assert_eq!(map.rank(&500), oracle.len());
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Sum(i64);
+
+impl Augment<i32, i32> for Sum {
+    fn from_entry(_key: &i32, value: &i32) -> Self {
+        Self(*value as i64)
+    }
+
+    fn combine(&self, right: &Self) -> Self {
+        Self(self.0 + right.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct MinMax(i32, i32);
+
+impl Augment<i32, i32> for MinMax {
+    fn from_entry(_key: &i32, value: &i32) -> Self {
+        Self(*value, *value)
+    }
+
+    fn combine(&self, right: &Self) -> Self {
+        Self(self.0.min(right.0), self.1.max(right.1))
+    }
+}
+
+#[test]
+fn test_aggregate() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut sums = AvlMap::<i32, i32, Sum>::augmented();
+    let mut min_maxes = AvlMap::<i32, i32, MinMax>::augmented();
+    let mut oracle = BTreeMap::new();
+
+    for _ in 0..5_000 {
+        let k = rng.gen_range(0..500);
+        let v = rng.gen_range(-1_000..1_000);
+        if rng.gen_bool(0.7) {
+            assert_eq!(sums.insert(k, v), oracle.insert(k, v));
+            min_maxes.insert(k, v);
+        } else {
+            assert_eq!(sums.remove(&k), oracle.remove(&k));
+            min_maxes.remove(&k);
+        }
+        let end = k + rng.gen_range(0..100);
+        let values: Vec<_> = oracle.range(k..end).map(|(_, v)| *v).collect();
+        let sum = values.iter().map(|v| *v as i64).sum();
+        assert_eq!(
+            sums.aggregate(k..end).map(|s| s.0),
+            (!values.is_empty()).then_some(sum)
+        );
+        let min_max = values.iter().min().zip(values.iter().max());
+        assert_eq!(
+            min_maxes.aggregate(k..end),
+            min_max.map(|(min, max)| MinMax(*min, *max))
+        );
+    }
+    let total = oracle.values().map(|v| *v as i64).sum();
+    assert_eq!(sums.aggregate(..), Some(Sum(total)));
+    assert_eq!(
+        sums.aggregate((Bound::Excluded(499), Bound::Unbounded)),
+        None
+    );
+}
+
+#[test]
+fn test_from_sorted_iter() {
+    for len in [0, 1, 2, 3, 7, 8, 1_000, 1_023, 1_024] {
+        let map = AvlMap::<i32, i32, Sum>::from_sorted_iter((0..len).map(|k| (k, k)));
+        map.check_invariants();
+        assert_eq!(map.len(), len as usize);
+        assert!(map.iter().map(|(k, _)| *k).eq(0..len));
+        // perfectly balanced, e.g. ceil(log2(len + 1)) high.
+        let height = usize::BITS - (len as usize).leading_zeros();
+        assert_eq!(map.height(), height as usize, "len {len}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "unsorted keys")]
+fn test_from_sorted_iter_unsorted() {
+    AvlMap::<i32, i32>::from_sorted_iter([(1, 1), (3, 3), (3, 3)]);
+}
+
+/// Returns the random map and its oracle.
+fn random_map(
+    rng: &mut StdRng,
+    len: usize,
+    max: i32,
+) -> (AvlMap<i32, i32, Sum>, BTreeMap<i32, i32>) {
+    let mut map = AvlMap::augmented();
+    let mut oracle = BTreeMap::new();
+    for _ in 0..len {
+        let (k, v) = (rng.gen_range(0..max), rng.gen_range(0..1_000));
+        map.insert(k, v);
+        oracle.insert(k, v);
+    }
+    (map, oracle)
+}
+
+#[test]
+fn test_split_join() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for _ in 0..200 {
+        let len = rng.gen_range(0..300);
+        let (map, mut oracle) = random_map(&mut rng, len, 500);
+        let key = rng.gen_range(-10..510);
+
+        let (left, entry, right) = map.split(&key);
+        left.check_invariants();
+        right.check_invariants();
+        let mut greater = oracle.split_off(&key);
+        assert_eq!(entry, greater.remove_entry(&key));
+        assert!(left.iter().eq(oracle.iter()));
+        assert!(right.iter().eq(greater.iter()));
+
+        let map = left.join(right);
+        map.check_invariants();
+        oracle.append(&mut greater);
+        // without the split key.
+        assert!(map.iter().eq(oracle.iter()));
+    }
+}
+
+#[test]
+fn test_split_off() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for _ in 0..200 {
+        let len = rng.gen_range(0..300);
+        let (mut map, mut oracle) = random_map(&mut rng, len, 500);
+        let key = rng.gen_range(-10..510);
+        let right = map.split_off(&key);
+        let greater = oracle.split_off(&key);
+        map.check_invariants();
+        right.check_invariants();
+        assert!(map.iter().eq(oracle.iter()));
+        assert!(right.iter().eq(greater.iter()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "overlapping keys")]
+fn test_join_overlapping() {
+    map_from(&[1, 5]).join(map_from(&[3, 7]));
+}
+
+#[test]
+fn test_set_operations() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for _ in 0..200 {
+        let (a_len, b_len) = (rng.gen_range(0..300), rng.gen_range(0..300));
+        let max = rng.gen_range(1..1_000);
+        let (a, a_oracle) = random_map(&mut rng, a_len, max);
+        let (b, b_oracle) = random_map(&mut rng, b_len, max);
+        let copy = |map: &AvlMap<i32, i32, Sum>| {
+            AvlMap::<i32, i32, Sum>::from_sorted_iter(map.iter().map(|(k, v)| (*k, *v)))
+        };
+
+        let union = copy(&a).union(copy(&b));
+        union.check_invariants();
+        let mut expected = a_oracle.clone();
+        expected.extend(b_oracle.iter());
+        assert!(union.iter().eq(expected.iter()));
+
+        let intersection = copy(&a).intersection(copy(&b));
+        intersection.check_invariants();
+        assert!(intersection
+            .iter()
+            .eq(a_oracle.iter().filter(|(k, _)| b_oracle.contains_key(k))));
+
+        let difference = copy(&a).difference(copy(&b));
+        difference.check_invariants();
+        assert!(difference
+            .iter()
+            .eq(a_oracle.iter().filter(|(k, _)| !b_oracle.contains_key(k))));
+
+        let (mut a, mut b) = (a, b);
+        a.append(&mut b);
+        a.check_invariants();
+        assert!(b.is_empty());
+        assert!(a.iter().eq(expected.iter()));
+    }
+}
+
+#[test]
+fn test_ordered_map_conformance() {
+    ordered_map::conformance::check(AvlMap::new);
+}
+
+#[test]
+fn test_augmented_ordered_map_conformance() {
+    ordered_map::conformance::check(AvlMap::<i32, i32, Sum>::augmented);
+}
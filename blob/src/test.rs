@@ -21,6 +21,7 @@ fn test_write_read_str() {
         let mut fout = fs::OpenOptions::new()
             .write(true)
             .create(true)
+            .truncate(true)
             .open(test)
             .unwrap();
         blob.write(&mut fout).unwrap();
@@ -9,25 +9,94 @@ use std::hash::Hash;
 pub struct BucketList<K, V> {
     seed: u64,
     len: usize,
-    pub buckets: Vec<Vec<(K, V)>>,
+    max_chain: Option<usize>,
+    pushes: usize,
+    buckets: Vec<Vec<(K, V)>>,
+}
+
+/// A bucket occupancy statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    /// The number of entries.
+    pub len: usize,
+    /// The number of buckets.
+    pub buckets: usize,
+    /// The longest chain length.
+    pub longest: usize,
+    /// The number of buckets indexed by the chain length.
+    pub histogram: Vec<usize>,
+    /// The average chain length.
+    pub load_factor: f64,
 }
 
 impl<K, V> Default for BucketList<K, V> {
     fn default() -> Self {
+        Self::with_seed(rand::random())
+    }
+}
+
+impl<K, V> BucketList<K, V> {
+    /// Creates a bucket list with the explicit hash seed, so that
+    /// the bucket layout is reproducible.
+    pub fn with_seed(seed: u64) -> Self {
         Self {
-            seed: rand::random(),
+            seed,
             len: 0,
+            max_chain: None,
+            pushes: 0,
             buckets: vec![Vec::new()],
         }
     }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the bucket occupancy statistics.
+    pub fn stats(&self) -> Stats {
+        let longest = self.buckets.iter().map(Vec::len).max().unwrap_or(0);
+        let mut histogram = vec![0; longest + 1];
+        for b in &self.buckets {
+            histogram[b.len()] += 1;
+        }
+        Stats {
+            len: self.len,
+            buckets: self.buckets.len(),
+            longest,
+            histogram,
+            load_factor: self.len as f64 / self.buckets.len() as f64,
+        }
+    }
+
+    /// Returns the iterator over the key/value pairs in the bucket order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.buckets.iter().flatten().map(|(k, v)| (k, v))
+    }
+
+    /// Guards the list against the overlong collision chains.
+    ///
+    /// Once a bucket gets more than `max` entries above the average
+    /// bucket load, the list rehashes itself with a new random seed
+    /// before pushing to it, so that the attacker chosen keys can't
+    /// pile up in one bucket.  The rehash happens at most once every
+    /// `len / 2` pushes to keep the push amortized O(1), even if the
+    /// keys collide under any seed.
+    pub fn set_max_chain(&mut self, max: usize) {
+        self.max_chain = Some(max);
+    }
 }
 
 impl<K: Eq + Hash, V> BucketList<K, V> {
     /// Pushes the new key/value and returns the current size of the bucket.
     pub fn push(&mut self, k: K, v: V) -> usize {
-        let h = (hasher::hash(self.seed, &k) as usize) % self.buckets.len();
+        let mut h = self.index(&k);
+        if self.is_overlong(h) {
+            self.reseed(rand::random());
+            h = self.index(&k);
+        }
         self.buckets[h].push((k, v));
         self.len += 1;
+        self.pushes += 1;
         self.buckets[h].len()
     }
 
@@ -44,7 +113,7 @@ impl<K: Eq + Hash, V> BucketList<K, V> {
         K: Borrow<Q>,
         Q: Eq + Hash + ?Sized,
     {
-        let h = (hasher::hash(self.seed, k) as usize) % self.buckets.len();
+        let h = self.index(k);
         for (ik, iv) in &self.buckets[h] {
             if k == ik.borrow() {
                 return Some(iv);
@@ -58,7 +127,7 @@ impl<K: Eq + Hash, V> BucketList<K, V> {
         K: BorrowMut<Q>,
         Q: Eq + Hash + ?Sized,
     {
-        let h = (hasher::hash(self.seed, k) as usize) % self.buckets.len();
+        let h = self.index(k);
         for (ik, iv) in &mut self.buckets[h] {
             if k == ik.borrow_mut() {
                 return Some(iv);
@@ -67,6 +136,25 @@ impl<K: Eq + Hash, V> BucketList<K, V> {
         None
     }
 
+    /// Removes the key and returns the value if it was in the list.
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let h = self.index(k);
+        let i = self.buckets[h]
+            .iter()
+            .position(|(ik, _)| k == ik.borrow())?;
+        self.len -= 1;
+        Some(self.buckets[h].swap_remove(i).1)
+    }
+
+    /// Rehashes all the entries with the new seed.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rehash(seed, self.buckets.len());
+    }
+
     pub fn bucket(&mut self, n: usize) -> Option<Vec<(K, V)>> {
         if n >= self.buckets.len() {
             return None;
@@ -86,4 +174,34 @@ impl<K: Eq + Hash, V> BucketList<K, V> {
             self.buckets.push(Vec::new());
         }
     }
+
+    /// Rehashes all the entries into `n` buckets with the seed.
+    fn rehash(&mut self, seed: u64, n: usize) {
+        let buckets = std::mem::replace(&mut self.buckets, (0..n).map(|_| Vec::new()).collect());
+        self.seed = seed;
+        self.pushes = 0;
+        for (k, v) in buckets.into_iter().flatten() {
+            let h = self.index(&k);
+            self.buckets[h].push((k, v));
+        }
+    }
+
+    fn index<Q: Hash + ?Sized>(&self, k: &Q) -> usize {
+        (hasher::hash(self.seed, k) as usize) % self.buckets.len()
+    }
+
+    fn is_overlong(&self, h: usize) -> bool {
+        match self.max_chain {
+            Some(max) => {
+                self.buckets[h].len() >= max + self.len / self.buckets.len()
+                    && self.pushes >= self.len / 2
+            }
+            None => false,
+        }
+    }
 }
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(test)]
+mod test;
@@ -0,0 +1,71 @@
+//! Serde support, serializing the bucket list as a map.
+//!
+//! Neither the seed nor the bucket layout is serialized, so the
+//! deserialized list hashes its entries with a new seed.  It doubles its
+//! buckets as the entries arrive to keep one bucket per entry, and keeps
+//! the last value of the duplicate keys as the std maps do.
+
+use std::cmp;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::mem;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::BucketList;
+
+impl<K: Serialize, V: Serialize> Serialize for BucketList<K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len))?;
+        for (k, v) in self.iter() {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for BucketList<K, V>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(BucketListVisitor(PhantomData))
+    }
+}
+
+struct BucketListVisitor<K, V>(PhantomData<(K, V)>);
+
+impl<'de, K, V> Visitor<'de> for BucketListVisitor<K, V>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+{
+    type Value = BucketList<K, V>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut list = BucketList::default();
+        // caps the preallocation like serde's `size_hint::cautious`, as
+        // the declared size may be hostile.
+        let cap = 1024 * 1024 / mem::size_of::<Vec<(K, V)>>();
+        list.set_buckets(cmp::min(access.size_hint().unwrap_or(0), cap));
+        while let Some((k, v)) = access.next_entry()? {
+            if let Some(old) = list.get_mut(&k) {
+                *old = v;
+                continue;
+            }
+            if list.len >= list.buckets.len() {
+                let seed = list.seed;
+                list.rehash(seed, list.buckets.len() * 2);
+            }
+            list.push(k, v);
+        }
+        Ok(list)
+    }
+}
@@ -0,0 +1,172 @@
+use super::BucketList;
+
+// Returns `n` keys which end up in the second bucket for the `seed`.
+fn colliding_keys(seed: u64, buckets: usize, n: usize) -> Vec<u64> {
+    (0..)
+        .filter(|k| hasher::hash(seed, k) as usize % buckets == 1)
+        .take(n)
+        .collect()
+}
+
+#[test]
+fn test_same_seed_same_layout() {
+    let mut a = BucketList::with_seed(55);
+    let mut b = BucketList::with_seed(55);
+    a.set_buckets(16);
+    b.set_buckets(16);
+    for x in 0..1_000 {
+        a.push(x, x);
+        b.push(x, x);
+    }
+    assert!(a.iter().eq(b.iter()));
+}
+
+#[test]
+fn test_reseed() {
+    let mut list = BucketList::with_seed(55);
+    list.set_buckets(16);
+    for x in 0..1_000 {
+        list.push(x, x + 250);
+    }
+    let before: Vec<_> = list.iter().map(|(k, v)| (*k, *v)).collect();
+
+    list.reseed(56);
+    assert_eq!(list.seed(), 56);
+    assert_eq!(list.len(), 1_000);
+    assert!(!list.iter().map(|(k, v)| (*k, *v)).eq(before));
+    for x in 0..1_000 {
+        assert_eq!(list.get(&x), Some(&(x + 250)));
+    }
+}
+
+#[test]
+fn test_remove() {
+    let mut list = BucketList::with_seed(55);
+    list.set_buckets(4);
+    for x in 0..100 {
+        list.push(x, x + 250);
+    }
+    assert_eq!(list.remove(&10), Some(260));
+    assert_eq!(list.remove(&10), None);
+    assert_eq!(list.len(), 99);
+    assert_eq!(list.get(&10), None);
+    assert_eq!(list.get(&11), Some(&261));
+}
+
+#[test]
+fn test_colliding_keys_without_max_chain() {
+    let keys = colliding_keys(55, 64, 200);
+    let mut list = BucketList::with_seed(55);
+    list.set_buckets(64);
+    for k in &keys {
+        list.push(*k, ());
+    }
+    assert_eq!(list.stats().longest, 200);
+}
+
+#[test]
+fn test_colliding_keys_with_max_chain() {
+    let keys = colliding_keys(55, 64, 200);
+    let mut list = BucketList::with_seed(55);
+    list.set_buckets(64);
+    list.set_max_chain(8);
+    for k in &keys {
+        list.push(*k, *k);
+    }
+    assert_ne!(list.seed(), 55);
+    assert_eq!(list.len(), 200);
+    assert!(list.stats().longest < 20, "{}", list.stats().longest);
+    for k in &keys {
+        assert_eq!(list.get(k), Some(k));
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip() {
+    let mut list = BucketList::with_seed(55);
+    list.set_buckets(8);
+    for x in 0..100 {
+        list.push(x, x + 250);
+    }
+
+    let data = bincode::serialize(&list).unwrap();
+    let got: BucketList<i32, i32> = bincode::deserialize(&data).unwrap();
+    assert_eq!(got.len(), 100);
+    for x in 0..100 {
+        assert_eq!(got.get(&x), Some(&(x + 250)));
+    }
+
+    // It's just a map in the serialized form.
+    let got: std::collections::HashMap<i32, i32> = bincode::deserialize(&data).unwrap();
+    assert_eq!(got.len(), 100);
+    assert_eq!(got.get(&0), Some(&250));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_duplicate_keys() {
+    // a sequence of pairs is encoded as a map by bincode.
+    let data = bincode::serialize(&vec![(1, 10), (2, 20), (1, 11)]).unwrap();
+    let got: BucketList<i32, i32> = bincode::deserialize(&data).unwrap();
+    assert_eq!(got.len(), 2);
+    assert_eq!(got.get(&1), Some(&11));
+    assert_eq!(got.get(&2), Some(&20));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_hostile_len() {
+    let data = bincode::serialize(&u64::MAX).unwrap();
+    assert!(bincode::deserialize::<BucketList<i32, i32>>(&data).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_without_size_hint() {
+    use serde::de::value::{Error, MapDeserializer};
+    use serde::Deserialize;
+
+    // the filter hides the exact size of the entries.
+    let entries = (0..20_000).map(|x| (x, x + 250)).filter(|_| true);
+    let got = BucketList::<i32, i32>::deserialize(MapDeserializer::<_, Error>::new(entries));
+    let got = got.unwrap();
+    assert_eq!(got.len(), 20_000);
+    assert!(got.stats().buckets >= 10_000);
+    assert!(got.stats().longest < 20);
+    for x in 0..20_000 {
+        assert_eq!(got.get(&x), Some(&(x + 250)));
+    }
+}
+
+#[test]
+fn test_stats() {
+    let mut list = BucketList::with_seed(55);
+    let stats = list.stats();
+    assert_eq!(stats.len, 0);
+    assert_eq!(stats.buckets, 1);
+    assert_eq!(stats.longest, 0);
+    assert_eq!(stats.histogram, vec![1]);
+    assert_eq!(stats.load_factor, 0.0);
+
+    list.set_buckets(64);
+    for k in colliding_keys(55, 64, 5) {
+        list.push(k, ());
+    }
+    list.push(0, ());
+    let stats = list.stats();
+    assert_eq!(stats.len, 6);
+    assert_eq!(stats.buckets, 64);
+    assert!(stats.longest >= 5);
+    assert_eq!(stats.histogram.iter().sum::<usize>(), 64);
+    assert_eq!(
+        stats
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(n, b)| n * b)
+            .sum::<usize>(),
+        6
+    );
+    assert_eq!(stats.load_factor, 6.0 / 64.0);
+}
@@ -0,0 +1,102 @@
+//! Benchmarks the raw pointer list against the `Rc` one, the arena one and
+//! the std one.
+
+use std::collections::LinkedList as StdList;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use dlist::arena::LinkedList as ArenaList;
+use dlist::raw::LinkedList as RawList;
+use dlist::LinkedList as RcList;
+
+const N: usize = 10_000;
+
+/// The lists under the benchmarks.
+trait List: Default {
+    fn push_front(&mut self, data: usize);
+    fn push_back(&mut self, data: usize);
+    fn pop_front(&mut self) -> Option<usize>;
+    fn pop_back(&mut self) -> Option<usize>;
+    fn sum(&self) -> usize;
+}
+
+macro_rules! impl_list {
+    ($list:ty) => {
+        impl_list!($list, |data| *data);
+    };
+    // reads the element yielded by the iterator.
+    ($list:ty, |$data:ident| $read:expr) => {
+        impl List for $list {
+            fn push_front(&mut self, data: usize) {
+                <$list>::push_front(self, data)
+            }
+
+            fn push_back(&mut self, data: usize) {
+                <$list>::push_back(self, data)
+            }
+
+            fn pop_front(&mut self) -> Option<usize> {
+                <$list>::pop_front(self)
+            }
+
+            fn pop_back(&mut self) -> Option<usize> {
+                <$list>::pop_back(self)
+            }
+
+            fn sum(&self) -> usize {
+                self.iter().map(|$data| $read).sum()
+            }
+        }
+    };
+}
+
+impl_list!(RawList<usize>);
+impl_list!(RcList<usize>, |data| *data.borrow());
+impl_list!(ArenaList<usize>);
+impl_list!(StdList<usize>);
+
+fn filled<L: List>() -> L {
+    let mut list = L::default();
+    for i in 0..N {
+        if i % 2 == 1 {
+            list.push_front(i);
+        } else {
+            list.push_back(i);
+        }
+    }
+    list
+}
+
+fn bench<L: List>(c: &mut Criterion, name: &str) {
+    c.bench_function(&format!("push/{name}"), |b| b.iter(filled::<L>));
+    c.bench_function(&format!("push_pop/{name}"), |b| {
+        b.iter_batched(
+            filled::<L>,
+            |mut list| {
+                // keeps the list at the same length while churning both ends.
+                for i in 0..N {
+                    list.push_back(i);
+                    black_box(list.pop_front());
+                    list.push_front(i);
+                    black_box(list.pop_back());
+                }
+                list
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    let list = filled::<L>();
+    c.bench_with_input(BenchmarkId::new("iter", name), &list, |b, list| {
+        b.iter(|| black_box(list.sum()))
+    });
+}
+
+fn list(c: &mut Criterion) {
+    bench::<RawList<usize>>(c, "raw");
+    bench::<RcList<usize>>(c, "rc");
+    bench::<ArenaList<usize>>(c, "arena");
+    bench::<StdList<usize>>(c, "std");
+}
+
+criterion_group!(benches, list);
+criterion_main!(benches);
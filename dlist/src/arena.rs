@@ -0,0 +1,778 @@
+//! A doubly linked list in an arena
+//!
+//! It's the same list as [`LinkedList`], of which nodes are kept in a
+//! single `Vec` and link each other by the index, and of which elements
+//! are reachable by the [`Handle`] in O(1), e.g. to move the element to
+//! either end as an LRU cache does.
+//!
+//! [`LinkedList`]: crate::LinkedList
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+// The generation of the next node, unique across all the lists not to
+// mistake a node reusing the index for the removed one.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// A doubly linked list of which nodes are kept in the arena.
+///
+/// The nodes link each other by the index into the arena, and the removed
+/// nodes are reused by the next push, e.g. the pushes and the pops don't
+/// allocate but for the growth of the arena.
+pub struct LinkedList<T> {
+    nodes: Vec<Option<Node<T>>>,
+    /// The removed nodes to be reused.
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.push_back(data);
+        }
+    }
+}
+
+impl<T> LinkedList<T> {
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    pub fn push_front(&mut self, data: T) {
+        let node = self.alloc(data, None, self.head);
+        match self.head {
+            Some(head) => self.node_mut(head).prev = Some(node),
+            None => self.tail = Some(node),
+        }
+        self.head = Some(node);
+    }
+
+    pub fn push_back(&mut self, data: T) {
+        let node = self.alloc(data, self.tail, None);
+        match self.tail {
+            Some(tail) => self.node_mut(tail).next = Some(node),
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|head| self.unlink(head))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|tail| self.unlink(tail))
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.head.map(|head| &self.node(head).data)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.tail.map(|tail| &self.node(tail).data)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.head.map(move |head| &mut self.node_mut(head).data)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.tail.map(move |tail| &mut self.node_mut(tail).data)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Moves all the elements of the `other` to the back of the list.
+    ///
+    /// It takes O(m) for the `m` elements of the `other`, as they move
+    /// into the arena of the list, unless the list is empty.
+    pub fn append(&mut self, other: &mut Self) {
+        if self.is_empty() {
+            mem::swap(self, other);
+            return;
+        }
+        while let Some(data) = other.pop_front() {
+            self.push_back(data);
+        }
+        other.clear();
+    }
+
+    /// Splits the list into two at the index, and returns the elements
+    /// from the index on.
+    ///
+    /// It takes O(n - at), as the elements move into the arena of the
+    /// new list.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `at` is greater than the length.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split index (is {at}) should be <= len");
+        let mut other = Self::new();
+        while self.len > at {
+            other.push_front(self.pop_back().unwrap());
+        }
+        other
+    }
+
+    /// Sorts the list in place in O(n log n), keeping the order of
+    /// the equal elements.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list with the comparator by the bottom-up merge sort,
+    /// which relinks the nodes without moving the elements in the arena.
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // the sorted runs of 2^i nodes linked by `next`, of which higher
+        // ones hold the earlier nodes.
+        let mut runs: Vec<Option<usize>> = Vec::new();
+        let mut next = self.head;
+        while let Some(node) = next {
+            next = self.node_mut(node).next.take();
+            let mut carry = Some(node);
+            for run in runs.iter_mut() {
+                match run.take() {
+                    Some(run) => carry = self.merge(Some(run), carry, &mut cmp),
+                    None => {
+                        *run = carry.take();
+                        break;
+                    }
+                }
+            }
+            if carry.is_some() {
+                runs.push(carry);
+            }
+        }
+        let mut sorted = None;
+        for run in runs {
+            sorted = self.merge(run, sorted, &mut cmp);
+        }
+        self.relink(sorted);
+    }
+
+    /// Removes the consecutive equal elements, e.g. all the duplicates
+    /// of the sorted list.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut next = self.head;
+        while let Some(node) = next {
+            next = self.node(node).next;
+            while let Some(dup) = next.filter(|&dup| self.node(dup).data == self.node(node).data) {
+                next = self.node(dup).next;
+                self.unlink(dup);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            nodes: &self.nodes,
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            nodes: self.nodes.as_mut_ptr(),
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the handle of the front element.
+    pub fn front_handle(&self) -> Option<Handle> {
+        self.head.map(|head| self.handle(head))
+    }
+
+    /// Returns the handle of the back element.
+    pub fn back_handle(&self) -> Option<Handle> {
+        self.tail.map(|tail| self.handle(tail))
+    }
+
+    /// Returns the element of the handle, or `None` once it's removed.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        let node = self.nodes.get(handle.index)?.as_ref()?;
+        (node.generation == handle.generation).then_some(&node.data)
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        let node = self.nodes.get_mut(handle.index)?.as_mut()?;
+        (node.generation == handle.generation).then_some(&mut node.data)
+    }
+
+    /// Removes the element of the handle in O(1).
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        self.get(handle)?;
+        Some(self.unlink(handle.index))
+    }
+
+    /// Moves the element of the handle to the front in O(1).
+    pub fn move_to_front(&mut self, handle: Handle) {
+        let index = handle.index;
+        if self.get(handle).is_none() || self.head == Some(index) {
+            return;
+        }
+        self.detach(index);
+        let head = self.head;
+        let node = self.node_mut(index);
+        node.prev = None;
+        node.next = head;
+        match head {
+            Some(head) => self.node_mut(head).prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+
+    /// Moves the element of the handle to the back in O(1).
+    pub fn move_to_back(&mut self, handle: Handle) {
+        let index = handle.index;
+        if self.get(handle).is_none() || self.tail == Some(index) {
+            return;
+        }
+        self.detach(index);
+        let tail = self.tail;
+        let node = self.node_mut(index);
+        node.prev = tail;
+        node.next = None;
+        match tail {
+            Some(tail) => self.node_mut(tail).next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+    }
+
+    /// Frees the removed nodes at the end of the arena, and shrinks
+    /// the capacity as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        while let Some(None) = self.nodes.last() {
+            self.nodes.pop();
+        }
+        let len = self.nodes.len();
+        self.free.retain(|&index| index < len);
+        self.nodes.shrink_to_fit();
+        self.free.shrink_to_fit();
+    }
+
+    /// Returns the cursor at the front, or at the "ghost" non-element
+    /// in case the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns the cursor at the back, or at the "ghost" non-element
+    /// in case the list is empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Returns the node for the data, reusing the removed one if any.
+    fn alloc(&mut self, data: T, prev: Option<usize>, next: Option<usize>) -> usize {
+        let node = Some(Node {
+            data,
+            prev,
+            next,
+            generation: GENERATION.fetch_add(1, Relaxed),
+        });
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Removes the node from the list and returns the data.
+    fn unlink(&mut self, index: usize) -> T {
+        self.detach(index);
+        let node = self.nodes[index].take().expect("link to removed node");
+        self.free.push(index);
+        self.len -= 1;
+        node.data
+    }
+
+    /// Links the neighbors of the node to each other, leaving the node
+    /// out of the list.
+    fn detach(&mut self, index: usize) {
+        let Node { prev, next, .. } = *self.node(index);
+        match prev {
+            Some(prev) => self.node_mut(prev).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// Merges the two sorted chains of the nodes linked by `next`.
+    fn merge<F>(&mut self, mut a: Option<usize>, mut b: Option<usize>, cmp: &mut F) -> Option<usize>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut head = None;
+        let mut tail = None;
+        while let (Some(x), Some(y)) = (a, b) {
+            // takes the one of `a` unless `b` is less for the stability.
+            let node = if cmp(&self.node(y).data, &self.node(x).data) == Ordering::Less {
+                b = self.node(y).next;
+                y
+            } else {
+                a = self.node(x).next;
+                x
+            };
+            match tail {
+                Some(tail) => self.node_mut(tail).next = Some(node),
+                None => head = Some(node),
+            }
+            tail = Some(node);
+        }
+        let rest = a.or(b);
+        match tail {
+            Some(tail) => self.node_mut(tail).next = rest,
+            None => head = rest,
+        }
+        head
+    }
+
+    /// Makes the chain of the nodes linked by `next` the list, fixing
+    /// the `prev` links and the tail.
+    fn relink(&mut self, head: Option<usize>) {
+        self.head = head;
+        let mut prev = None;
+        let mut next = head;
+        while let Some(index) = next {
+            let node = self.node_mut(index);
+            node.prev = prev;
+            next = node.next;
+            prev = Some(index);
+        }
+        self.tail = prev;
+    }
+
+    fn handle(&self, index: usize) -> Handle {
+        Handle {
+            index,
+            generation: self.node(index).generation,
+        }
+    }
+
+    fn node(&self, index: usize) -> &Node<T> {
+        self.nodes[index].as_ref().expect("link to removed node")
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut Node<T> {
+        self.nodes[index].as_mut().expect("link to removed node")
+    }
+}
+
+/// Merges the two sorted lists into one sorted list, taking the element
+/// of `a` first for the equal ones.
+///
+/// The elements of the shorter list move into the arena of the longer one
+/// in O(min(n, m)), and then the nodes are merged by relinking them.
+pub fn merge_sorted<T: Ord>(mut a: LinkedList<T>, mut b: LinkedList<T>) -> LinkedList<T> {
+    // the last node of the elements of `a` in the merged arena.
+    let (mut list, last) = if a.len >= b.len {
+        let last = a.tail;
+        a.append(&mut b);
+        (a, last)
+    } else {
+        let first = b.head;
+        while let Some(data) = a.pop_back() {
+            b.push_front(data);
+        }
+        let last = first.and_then(|first| b.node(first).prev);
+        (b, last)
+    };
+    if let Some(last) = last {
+        let second = list.node_mut(last).next.take();
+        let head = list.merge(list.head, second, &mut T::cmp);
+        list.relink(head);
+    }
+    list
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+/// A handle to the element of [`LinkedList`].
+///
+/// It stays valid until the element is removed, after which it refers to
+/// nothing even if the other element reuses the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: u64,
+}
+
+/// An iterator over the elements of [`LinkedList`].
+pub struct Iter<'a, T> {
+    nodes: &'a [Option<Node<T>>],
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn node(&self, index: usize) -> &'a Node<T> {
+        self.nodes[index].as_ref().expect("link to removed node")
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.node(self.head?);
+        self.head = node.next;
+        self.len -= 1;
+        Some(&node.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.node(self.tail?);
+        self.tail = node.prev;
+        self.len -= 1;
+        Some(&node.data)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over the mutable elements of [`LinkedList`].
+#[derive(Debug)]
+pub struct IterMut<'a, T> {
+    nodes: *mut Option<Node<T>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+// the iterator hands out the mutable elements, as `&mut [T]` does.
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> IterMut<'a, T> {
+    fn node(&mut self, index: usize) -> &'a mut Node<T> {
+        // SAFETY: the arena lives while the list is borrowed mutably, and
+        // each node is taken only once as the iterator stops after `len`
+        // nodes from either end.
+        unsafe { (*self.nodes.add(index)).as_mut() }.expect("link to removed node")
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.node(self.head?);
+        self.head = node.next;
+        self.len -= 1;
+        Some(&mut node.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.node(self.tail?);
+        self.tail = node.prev;
+        self.len -= 1;
+        Some(&mut node.data)
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of [`LinkedList`].
+#[derive(Debug)]
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// A cursor over [`LinkedList`] to edit the list at any position.
+///
+/// The cursor is at an element, or at the "ghost" non-element between
+/// the back and the front of the list, e.g. it moves to the front from
+/// the ghost and to the ghost from the back.
+#[derive(Debug)]
+pub struct CursorMut<'a, T> {
+    list: &'a mut LinkedList<T>,
+    current: Option<usize>,
+    index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Returns the index of the element, or `None` at the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        let current = self.current?;
+        Some(&mut self.list.node_mut(current).data)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(current) => {
+                self.current = self.list.node(current).next;
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(current) => {
+                self.current = self.list.node(current).prev;
+                self.index = self.index.saturating_sub(1);
+            }
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Returns the next element, or the front at the ghost.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(current) => self.list.node(current).next,
+            None => self.list.head,
+        }?;
+        Some(&mut self.list.node_mut(next).data)
+    }
+
+    /// Returns the previous element, or the back at the ghost.
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            Some(current) => self.list.node(current).prev,
+            None => self.list.tail,
+        }?;
+        Some(&mut self.list.node_mut(prev).data)
+    }
+
+    /// Inserts the element after the current one, or at the front
+    /// at the ghost.
+    pub fn insert_after(&mut self, data: T) {
+        let current = match self.current {
+            Some(current) => current,
+            None => return self.list.push_front(data),
+        };
+        let next = self.list.node(current).next;
+        let node = self.list.alloc(data, Some(current), next);
+        self.list.node_mut(current).next = Some(node);
+        match next {
+            Some(next) => self.list.node_mut(next).prev = Some(node),
+            None => self.list.tail = Some(node),
+        }
+    }
+
+    /// Inserts the element before the current one, or at the back
+    /// at the ghost.
+    pub fn insert_before(&mut self, data: T) {
+        let current = match self.current {
+            Some(current) => current,
+            None => return self.list.push_back(data),
+        };
+        let prev = self.list.node(current).prev;
+        let node = self.list.alloc(data, prev, Some(current));
+        self.list.node_mut(current).prev = Some(node);
+        match prev {
+            Some(prev) => self.list.node_mut(prev).next = Some(node),
+            None => self.list.head = Some(node),
+        }
+        self.index += 1;
+    }
+
+    /// Removes the current element and moves to the next one, or returns
+    /// `None` at the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current?;
+        self.current = self.list.node(current).next;
+        Some(self.list.unlink(current))
+    }
+}
+
+#[derive(Debug)]
+struct Node<T> {
+    data: T,
+    prev: Option<usize>,
+    next: Option<usize>,
+    /// Tells the node from the removed one of the same index.
+    generation: u64,
+}
+
+#[cfg(test)]
+mod test;
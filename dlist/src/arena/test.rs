@@ -0,0 +1,292 @@
+use super::{merge_sorted, LinkedList};
+
+use std::collections::VecDeque;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn test_push_pop() {
+    let mut list = LinkedList::new();
+    list.push_front(2);
+    list.push_front(1);
+    list.push_back(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.front(), Some(&1));
+    assert_eq!(list.back(), Some(&3));
+    *list.front_mut().unwrap() = 0;
+    *list.back_mut().unwrap() = 4;
+    assert_eq!(list.pop_front(), Some(0));
+    assert_eq!(list.pop_back(), Some(4));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_back(), None);
+    assert!(list.is_empty());
+    assert_eq!(list.front(), None);
+}
+
+#[test]
+fn test_iter() {
+    let mut list: LinkedList<_> = (0..5).collect();
+    assert!(list.iter().eq(&[0, 1, 2, 3, 4]));
+    assert!(list.iter().rev().eq(&[4, 3, 2, 1, 0]));
+    let mut iter = list.iter();
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+    for x in &mut list {
+        *x *= 10;
+    }
+    assert_eq!(format!("{list:?}"), "[0, 10, 20, 30, 40]");
+    assert_eq!(list.iter_mut().next_back(), Some(&mut 40));
+    assert!(list.into_iter().rev().eq([40, 30, 20, 10, 0]));
+}
+
+#[test]
+fn test_append_split_off() {
+    let mut list: LinkedList<_> = (0..3).collect();
+    let mut other: LinkedList<_> = (3..6).collect();
+    list.append(&mut other);
+    assert!(other.is_empty());
+    assert!(list.iter().copied().eq(0..6));
+
+    let tail = list.split_off(4);
+    assert!(list.iter().copied().eq(0..4));
+    assert!(tail.iter().copied().eq(4..6));
+    assert!(list.split_off(4).is_empty());
+    assert_eq!(list.split_off(0).len(), 4);
+    assert!(list.is_empty());
+
+    let mut empty = LinkedList::new();
+    empty.append(&mut (7..9).collect());
+    assert_eq!(empty, (7..9).collect());
+}
+
+#[test]
+#[should_panic(expected = "split index")]
+fn test_split_off_out_of_bounds() {
+    let mut list: LinkedList<_> = (0..3).collect();
+    list.split_off(4);
+}
+
+#[test]
+fn test_sort() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for n in [0, 1, 2, 3, 7, 64, 1_000] {
+        let mut oracle: Vec<u8> = (0..n).map(|_| rng.gen()).collect();
+        let mut list: LinkedList<_> = oracle.iter().copied().collect();
+        list.sort();
+        oracle.sort();
+        assert_eq!(list.len(), n);
+        assert!(list.iter().eq(oracle.iter()));
+        assert!(list.iter().rev().eq(oracle.iter().rev()));
+    }
+}
+
+#[test]
+fn test_sort_stable() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut oracle: Vec<(u8, usize)> = (0..1_000).map(|i| (rng.gen_range(0..10), i)).collect();
+    let mut list: LinkedList<_> = oracle.iter().copied().collect();
+    list.sort_by(|a, b| a.0.cmp(&b.0));
+    oracle.sort_by_key(|x| x.0);
+    assert!(list.iter().eq(oracle.iter()));
+}
+
+#[test]
+fn test_sort_keeps_handles() {
+    let mut list: LinkedList<_> = [3, 1, 2].into_iter().collect();
+    let front = list.front_handle().unwrap();
+    list.sort();
+    assert_eq!(list.get(front), Some(&3));
+    assert_eq!(list.back_handle(), Some(front));
+    list.push_back(4);
+    assert_eq!(format!("{list:?}"), "[1, 2, 3, 4]");
+}
+
+#[test]
+fn test_merge_sorted() {
+    let a: LinkedList<_> = [1, 3, 3, 5].into_iter().collect();
+    let b: LinkedList<_> = [0, 3, 4, 6, 7].into_iter().collect();
+    let mut list = merge_sorted(a, b);
+    assert_eq!(list.len(), 9);
+    assert_eq!(format!("{list:?}"), "[0, 1, 3, 3, 3, 4, 5, 6, 7]");
+    assert!(list.iter().rev().eq([7, 6, 5, 4, 3, 3, 3, 1, 0].iter()));
+    assert_eq!(list.back(), Some(&7));
+    list.push_back(8);
+    let list = merge_sorted(list, LinkedList::new());
+    assert_eq!(list.len(), 10);
+    let list = merge_sorted(LinkedList::new(), list);
+    assert_eq!(list.len(), 10);
+    assert_eq!(list.back(), Some(&8));
+
+    // the shorter `a` moves into the arena of `b`.
+    let a: LinkedList<_> = [2, 9].into_iter().collect();
+    let list = merge_sorted(a, list);
+    assert_eq!(list.nodes.len(), 12);
+    assert_eq!(format!("{list:?}"), "[0, 1, 2, 3, 3, 3, 4, 5, 6, 7, 8, 9]");
+    assert!(list
+        .iter()
+        .rev()
+        .copied()
+        .eq([9, 8, 7, 6, 5, 4, 3, 3, 3, 2, 1, 0]));
+}
+
+#[test]
+fn test_dedup() {
+    let mut list: LinkedList<_> = [1, 1, 2, 3, 3, 3, 1, 4, 4].into_iter().collect();
+    list.dedup();
+    assert_eq!(list.len(), 5);
+    assert_eq!(format!("{list:?}"), "[1, 2, 3, 1, 4]");
+    assert_eq!(list.back(), Some(&4));
+
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut oracle: Vec<u8> = (0..1_000).map(|_| rng.gen_range(0..50)).collect();
+    let mut list: LinkedList<_> = oracle.iter().copied().collect();
+    list.sort();
+    list.dedup();
+    oracle.sort();
+    oracle.dedup();
+    assert_eq!(list.len(), oracle.len());
+    assert!(list.iter().eq(oracle.iter()));
+    assert!(list.iter().rev().eq(oracle.iter().rev()));
+}
+
+#[test]
+fn test_cursor() {
+    let mut list: LinkedList<_> = (1..4).collect();
+    let mut cursor = list.cursor_front_mut();
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(cursor.current(), Some(&mut 1));
+    cursor.move_next();
+    assert_eq!(cursor.peek_prev(), Some(&mut 1));
+    assert_eq!(cursor.peek_next(), Some(&mut 3));
+    cursor.insert_before(10);
+    cursor.insert_after(20);
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(cursor.remove_current(), Some(2));
+    assert_eq!(cursor.current(), Some(&mut 20));
+    cursor.move_next();
+    cursor.move_next();
+    // at the ghost between the back and the front.
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.remove_current(), None);
+    assert_eq!(cursor.peek_next(), Some(&mut 1));
+    assert_eq!(cursor.peek_prev(), Some(&mut 3));
+    cursor.insert_after(0);
+    cursor.insert_before(4);
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(&mut 4));
+    assert_eq!(cursor.index(), Some(5));
+    assert!(list.iter().eq(&[0, 1, 10, 20, 3, 4]));
+
+    let mut cursor = list.cursor_back_mut();
+    assert_eq!(cursor.remove_current(), Some(4));
+    assert_eq!(cursor.index(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(&mut 3));
+    assert_eq!(cursor.index(), Some(4));
+    assert!(list.iter().eq(&[0, 1, 10, 20, 3]));
+}
+
+#[test]
+fn test_against_vec_deque() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut list = LinkedList::new();
+    let mut oracle = VecDeque::new();
+
+    for i in 0..10_000 {
+        match rng.gen_range(0..6) {
+            0 => {
+                list.push_front(i);
+                oracle.push_front(i);
+            }
+            1 => {
+                list.push_back(i);
+                oracle.push_back(i);
+            }
+            2 => assert_eq!(list.pop_front(), oracle.pop_front()),
+            3 => assert_eq!(list.pop_back(), oracle.pop_back()),
+            _ => {
+                // edits around the random position with the cursor.
+                let at = rng.gen_range(0..=oracle.len());
+                let mut cursor = list.cursor_front_mut();
+                for _ in 0..at {
+                    cursor.move_next();
+                }
+                if rng.gen_bool(0.5) {
+                    cursor.insert_before(i);
+                    oracle.insert(at, i);
+                } else {
+                    let removed = cursor.remove_current();
+                    let want = if at < oracle.len() {
+                        oracle.remove(at)
+                    } else {
+                        None
+                    };
+                    assert_eq!(removed, want);
+                }
+            }
+        }
+        assert_eq!(list.len(), oracle.len());
+        assert_eq!(list.front(), oracle.front());
+        assert_eq!(list.back(), oracle.back());
+    }
+    assert!(list.iter().eq(oracle.iter()));
+    assert!(list.iter().rev().eq(oracle.iter().rev()));
+}
+
+#[test]
+fn test_handles() {
+    let mut list: LinkedList<_> = (0..3).collect();
+    let first = list.front_handle().unwrap();
+    let last = list.back_handle().unwrap();
+    assert_eq!(list.get(first), Some(&0));
+    *list.get_mut(last).unwrap() = 20;
+
+    list.move_to_back(first);
+    assert!(list.iter().eq(&[1, 20, 0]));
+    list.move_to_front(last);
+    assert!(list.iter().eq(&[20, 1, 0]));
+    list.move_to_front(last);
+    assert!(list.iter().eq(&[20, 1, 0]));
+    assert!(list.iter().rev().eq(&[0, 1, 20]));
+
+    assert_eq!(list.remove(last), Some(20));
+    assert_eq!(list.remove(last), None);
+    assert_eq!(list.get(last), None);
+    assert!(list.iter().eq(&[1, 0]));
+    assert_eq!(list.front_handle().and_then(|h| list.get(h)), Some(&1));
+}
+
+#[test]
+fn test_stale_handle() {
+    let mut list: LinkedList<_> = (0..3).collect();
+    let front = list.front_handle().unwrap();
+    assert_eq!(list.pop_front(), Some(0));
+    // reuses the slot of the removed node.
+    list.push_front(10);
+    assert_eq!(list.get(front), None);
+    assert_eq!(list.get_mut(front), None);
+    assert_eq!(list.remove(front), None);
+    assert_ne!(list.front_handle(), Some(front));
+    assert!(list.iter().eq(&[10, 1, 2]));
+}
+
+#[test]
+fn test_shrink_to_fit() {
+    let mut list: LinkedList<_> = (0..100).collect();
+    let front = list.front_handle().unwrap();
+    for _ in 0..90 {
+        list.pop_back();
+    }
+    list.shrink_to_fit();
+    assert!(list.nodes.len() <= 10);
+    assert!(list.free.is_empty());
+    assert_eq!(list.get(front), Some(&0));
+    list.extend(10..20);
+    assert!(list.iter().copied().eq(0..20));
+    assert!(list.iter().rev().copied().eq((0..20).rev()));
+}
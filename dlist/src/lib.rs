@@ -1,71 +1,127 @@
 //! A doubly linked list.
+//!
+//! The nodes are shared by `Rc` from the previous node and by `Weak` from
+//! the next one, and the list holds both ends by `Rc`.  The elements are
+//! handed out in the `RefCell` guards, e.g. [`Ref`] from [`front`], and
+//! the iterators yield the [`Element`]s holding the nodes, which borrow
+//! the elements in turn.
+//!
+//! [`front`]: LinkedList::front
 
+#![deny(unsafe_code)]
 #![forbid(missing_debug_implementations)]
 
-use std::cell::RefCell;
-use std::fmt::Debug;
-use std::ops::{Deref, DerefMut};
+use std::cell::{Ref, RefCell, RefMut};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem;
 use std::rc::{Rc, Weak};
 
-#[derive(Debug)]
-pub struct LinkedList<T: Debug> {
-    head: Option<Rc<RefCell<Node<T>>>>,
-    tail: Option<Weak<RefCell<Node<T>>>>,
+#[allow(unsafe_code)]
+pub mod arena;
+#[allow(unsafe_code)]
+pub mod raw;
+
+pub struct LinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
 }
 
-impl<T: Debug> Default for LinkedList<T> {
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+type WeakLink<T> = Option<Weak<RefCell<Node<T>>>>;
+
+impl<T> Default for LinkedList<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T: Debug> LinkedList<T> {
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        // unlinks one by one not to recurse on the long list.
+        let mut link = self.head.take();
+        while let Some(node) = link {
+            link = node.borrow_mut().next.take();
+        }
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        let mut list = Self::new();
+        for element in self {
+            list.push_back(element.borrow().clone());
+        }
+        list
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.push_back(data);
+        }
+    }
+}
+
+impl<T> LinkedList<T> {
     pub const fn new() -> Self {
         Self {
             head: None,
             tail: None,
+            len: 0,
         }
     }
 
     pub fn push_front(&mut self, data: T) {
+        let node = Rc::new(RefCell::new(Node::from(data)));
         match self.head.take() {
             Some(head) => {
-                let node = Rc::new(RefCell::new(Node {
-                    data,
-                    next: Some(head.clone()),
-                    prev: None,
-                }));
-                let mut next = head.borrow_mut();
-                next.prev = Some(Rc::downgrade(&node));
-                self.head = Some(node);
-            }
-            None => {
-                let node = Rc::new(RefCell::new(Node::from(data)));
-                self.tail = Some(Rc::downgrade(&node));
-                self.head = Some(node);
+                head.borrow_mut().prev = Some(Rc::downgrade(&node));
+                node.borrow_mut().next = Some(head);
             }
+            None => self.tail = Some(node.clone()),
         }
+        self.head = Some(node);
+        self.len += 1;
     }
 
     pub fn push_back(&mut self, data: T) {
+        let node = Rc::new(RefCell::new(Node::from(data)));
         match self.tail.take() {
             Some(tail) => {
-                let node = Rc::new(RefCell::new(Node {
-                    data,
-                    next: None,
-                    prev: Some(tail.clone()),
-                }));
-                let prev = tail.upgrade().unwrap();
-                let mut prev = prev.borrow_mut();
-                self.tail = Some(Rc::downgrade(&node));
-                prev.next = Some(node);
-            }
-            None => {
-                let node = Rc::new(RefCell::new(Node::from(data)));
-                self.tail = Some(Rc::downgrade(&node));
-                self.head = Some(node);
+                node.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(node.clone());
             }
+            None => self.head = Some(node.clone()),
         }
+        self.tail = Some(node);
+        self.len += 1;
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
@@ -77,77 +133,730 @@ impl<T: Debug> LinkedList<T> {
                 }
                 None => self.tail = None,
             }
-            Rc::try_unwrap(node).ok().unwrap().into_inner().data
+            self.len -= 1;
+            into_data(node)
         })
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
-        self.tail.take().map(|tail| {
-            let node = tail.upgrade().unwrap();
-            match node.borrow_mut().prev.take() {
+        self.tail.take().map(|node| {
+            match node
+                .borrow_mut()
+                .prev
+                .take()
+                .and_then(|prev| prev.upgrade())
+            {
                 Some(prev) => {
-                    prev.upgrade().unwrap().borrow_mut().next = None;
+                    prev.borrow_mut().next = None;
                     self.tail = Some(prev);
                 }
                 None => self.head = None,
             }
-            Rc::try_unwrap(node).ok().unwrap().into_inner().data
+            self.len -= 1;
+            into_data(node)
         })
     }
 
+    pub fn front(&self) -> Option<Ref<'_, T>> {
+        self.head.as_deref().map(data)
+    }
+
+    pub fn back(&self) -> Option<Ref<'_, T>> {
+        self.tail.as_deref().map(data)
+    }
+
+    pub fn front_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.head.as_deref().map(data_mut)
+    }
+
+    pub fn back_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.tail.as_deref().map(data_mut)
+    }
+
     pub const fn is_empty(&self) -> bool {
         self.head.is_none()
     }
 
-    pub fn len(&self) -> usize {
-        self.iter().count()
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Moves all the elements of the `other` to the back of the list
+    /// in O(1).
+    pub fn append(&mut self, other: &mut Self) {
+        let tail = match self.tail.take() {
+            Some(tail) => tail,
+            None => return mem::swap(self, other),
+        };
+        match other.head.take() {
+            Some(head) => {
+                head.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(head);
+                self.tail = other.tail.take();
+                self.len += mem::take(&mut other.len);
+            }
+            None => self.tail = Some(tail),
+        }
+    }
+
+    /// Splits the list into two at the index, and returns the elements
+    /// from the index on.
+    ///
+    /// It takes O(min(at, n - at)) to walk to the index from the nearer
+    /// end.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `at` is greater than the length.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split index (is {at}) should be <= len");
+        if at == 0 {
+            return mem::take(self);
+        }
+        if at == self.len {
+            return Self::new();
+        }
+        // the last node staying in the list.
+        let mut last = self.head.clone().unwrap();
+        if at <= self.len / 2 {
+            for _ in 1..at {
+                let next = last.borrow().next.clone().unwrap();
+                last = next;
+            }
+        } else {
+            last = self.tail.clone().unwrap();
+            for _ in at..self.len {
+                let prev = last.borrow().prev.as_ref().and_then(Weak::upgrade).unwrap();
+                last = prev;
+            }
+        }
+        let head = last.borrow_mut().next.take().unwrap();
+        head.borrow_mut().prev = None;
+        let other = Self {
+            head: Some(head),
+            tail: self.tail.replace(last),
+            len: self.len - at,
+        };
+        self.len = at;
+        other
+    }
+
+    /// Sorts the list in place in O(n log n), keeping the order of
+    /// the equal elements.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list with the comparator by the bottom-up merge sort,
+    /// which relinks the nodes without moving the elements.
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // leaves the list empty rather than half sorted in case `cmp`
+        // panics.
+        let len = mem::take(&mut self.len);
+        self.tail = None;
+        // the sorted runs of 2^i nodes linked by `next`, of which higher
+        // ones hold the earlier nodes.
+        let mut runs: Vec<Link<T>> = Vec::new();
+        let mut next = self.head.take();
+        while let Some(node) = next {
+            next = node.borrow_mut().next.take();
+            let mut carry = Some(node);
+            for run in runs.iter_mut() {
+                match run.take() {
+                    Some(run) => carry = merge(Some(run), carry, &mut cmp),
+                    None => {
+                        *run = carry.take();
+                        break;
+                    }
+                }
+            }
+            if carry.is_some() {
+                runs.push(carry);
+            }
+        }
+        let mut sorted = None;
+        for run in runs {
+            sorted = merge(run, sorted, &mut cmp);
+        }
+        self.relink(sorted, len);
+    }
+
+    /// Removes the consecutive equal elements, e.g. all the duplicates
+    /// of the sorted list.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut len = mem::take(&mut self.len);
+        self.tail = None;
+        let head = self.head.take();
+        let mut next = head.clone();
+        while let Some(node) = next {
+            let mut current = node.borrow_mut();
+            while let Some(dup) = current.next.take() {
+                if dup.borrow().data != current.data {
+                    current.next = Some(dup);
+                    break;
+                }
+                current.next = dup.borrow_mut().next.take();
+                len -= 1;
+            }
+            next = current.next.clone();
+        }
+        self.relink(head, len);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.head.clone(),
+            tail: self.tail.clone(),
+            len: self.len,
+            _list: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head.clone(),
+            tail: self.tail.clone(),
+            len: self.len,
+            _list: PhantomData,
+        }
+    }
+
+    /// Returns the cursor at the front, or at the "ghost" non-element
+    /// in case the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let head = self.head.clone();
+        CursorMut::new(self, head, 0)
+    }
+
+    /// Returns the cursor at the back, or at the "ghost" non-element
+    /// in case the list is empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let (tail, index) = (self.tail.clone(), self.len.saturating_sub(1));
+        CursorMut::new(self, tail, index)
     }
 
-    pub fn iter(&self) -> ListIter<T> {
-        ListIter {
-            next: self.head.clone(),
+    /// Makes the chain of the `len` nodes linked by `next` the list,
+    /// fixing the `prev` links and the tail.
+    fn relink(&mut self, head: Link<T>, len: usize) {
+        let mut prev: Link<T> = None;
+        let mut next = head.clone();
+        while let Some(node) = next {
+            {
+                let mut current = node.borrow_mut();
+                current.prev = prev.as_ref().map(Rc::downgrade);
+                next = current.next.clone();
+            }
+            prev = Some(node);
         }
+        self.head = head;
+        self.tail = prev;
+        self.len = len;
     }
 }
 
-#[derive(Debug)]
-pub struct ListIter<T: Debug> {
-    next: Option<Rc<RefCell<Node<T>>>>,
+/// Merges the two sorted lists into one sorted list by relinking their
+/// nodes, taking the element of `a` first for the equal ones.
+pub fn merge_sorted<T: Ord>(mut a: LinkedList<T>, mut b: LinkedList<T>) -> LinkedList<T> {
+    let len = mem::take(&mut a.len) + mem::take(&mut b.len);
+    a.tail = None;
+    b.tail = None;
+    let head = merge(a.head.take(), b.head.take(), &mut T::cmp);
+    let mut list = LinkedList::new();
+    list.relink(head, len);
+    list
+}
+
+/// Merges the two sorted chains of the nodes linked by `next`.
+fn merge<T, F>(mut a: Link<T>, mut b: Link<T>, cmp: &mut F) -> Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut head = None;
+    let mut tail: Link<T> = None;
+    while let (Some(x), Some(y)) = (&a, &b) {
+        // takes the one of `a` unless `b` is less for the stability.
+        let node = if cmp(&y.borrow().data, &x.borrow().data) == Ordering::Less {
+            let node = b.take().unwrap();
+            b = node.borrow_mut().next.take();
+            node
+        } else {
+            let node = a.take().unwrap();
+            a = node.borrow_mut().next.take();
+            node
+        };
+        match tail {
+            Some(ref tail) => tail.borrow_mut().next = Some(node.clone()),
+            None => head = Some(node.clone()),
+        }
+        tail = Some(node);
+    }
+    let rest = a.or(b);
+    match tail {
+        Some(tail) => tail.borrow_mut().next = rest,
+        None => head = rest,
+    }
+    head
+}
+
+/// Returns the element of the node no longer linked from the list.
+fn into_data<T>(node: Rc<RefCell<Node<T>>>) -> T {
+    match Rc::try_unwrap(node) {
+        Ok(node) => node.into_inner().data,
+        Err(_) => unreachable!("node shared out of the list"),
+    }
+}
+
+fn data<T>(node: &RefCell<Node<T>>) -> Ref<'_, T> {
+    Ref::map(node.borrow(), |node| &node.data)
 }
 
-impl<T: Debug> Iterator for ListIter<T> {
-    type Item = Rc<RefCell<Node<T>>>;
+fn data_mut<T>(node: &RefCell<Node<T>>) -> RefMut<'_, T> {
+    RefMut::map(node.borrow_mut(), |node| &mut node.data)
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = Element<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = ElementMut<'a, T>;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+/// An element of [`LinkedList`] yielded by [`Iter`], which holds the node
+/// as long as the list is borrowed.
+pub struct Element<'a, T> {
+    node: Rc<RefCell<Node<T>>>,
+    _list: PhantomData<&'a LinkedList<T>>,
+}
+
+// keeps the list borrowed until the node is released, as the list takes
+// the elements out of only the nodes no one else holds.
+impl<T> Drop for Element<'_, T> {
+    fn drop(&mut self) {}
+}
+
+impl<T> Element<'_, T> {
+    pub fn borrow(&self) -> Ref<'_, T> {
+        data(&self.node)
+    }
+}
+
+impl<T: Debug> Debug for Element<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.borrow().fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Element<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        *self.borrow() == *other.borrow()
+    }
+}
+
+impl<T: PartialEq> PartialEq<T> for Element<'_, T> {
+    fn eq(&self, other: &T) -> bool {
+        *self.borrow() == *other
+    }
+}
+
+/// A mutable element of [`LinkedList`] yielded by [`IterMut`], which
+/// holds the node as long as the list is borrowed mutably.
+pub struct ElementMut<'a, T> {
+    node: Rc<RefCell<Node<T>>>,
+    _list: PhantomData<&'a mut LinkedList<T>>,
+}
+
+// keeps the list borrowed until the node is released.
+impl<T> Drop for ElementMut<'_, T> {
+    fn drop(&mut self) {}
+}
+
+impl<T> ElementMut<'_, T> {
+    pub fn borrow(&self) -> Ref<'_, T> {
+        data(&self.node)
+    }
+
+    pub fn borrow_mut(&mut self) -> RefMut<'_, T> {
+        data_mut(&self.node)
+    }
+}
+
+impl<T: Debug> Debug for ElementMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.borrow().fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq<T> for ElementMut<'_, T> {
+    fn eq(&self, other: &T) -> bool {
+        *self.borrow() == *other
+    }
+}
+
+/// An iterator over the elements of [`LinkedList`].
+pub struct Iter<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _list: PhantomData<&'a LinkedList<T>>,
+}
+
+// keeps the list borrowed until the nodes are released.
+impl<T> Drop for Iter<'_, T> {
+    fn drop(&mut self) {}
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+            tail: self.tail.clone(),
+            len: self.len,
+            _list: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = Element<'a, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.take().map(|node| {
-            self.next = node.borrow().next.clone();
-            node
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.head.take()?;
+        self.head = node.borrow().next.clone();
+        self.len -= 1;
+        Some(Element {
+            node,
+            _list: PhantomData,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.tail.take()?;
+        self.tail = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+        self.len -= 1;
+        Some(Element {
+            node,
+            _list: PhantomData,
         })
     }
 }
 
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over the mutable elements of [`LinkedList`].
+pub struct IterMut<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _list: PhantomData<&'a mut LinkedList<T>>,
+}
+
+// keeps the list borrowed until the nodes are released.
+impl<T> Drop for IterMut<'_, T> {
+    fn drop(&mut self) {}
+}
+
+impl<T> Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterMut").field("len", &self.len).finish()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = ElementMut<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.head.take()?;
+        self.head = node.borrow().next.clone();
+        self.len -= 1;
+        Some(ElementMut {
+            node,
+            _list: PhantomData,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.tail.take()?;
+        self.tail = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+        self.len -= 1;
+        Some(ElementMut {
+            node,
+            _list: PhantomData,
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of [`LinkedList`].
 #[derive(Debug)]
-pub struct Node<T: Debug> {
-    data: T,
-    next: Option<Rc<RefCell<Self>>>,
-    prev: Option<Weak<RefCell<Self>>>,
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
 }
 
-impl<T: Debug> Deref for Node<T> {
-    type Target = T;
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.data
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// A cursor over [`LinkedList`] to edit the list at any position.
+///
+/// The cursor is at an element, or at the "ghost" non-element between
+/// the back and the front of the list, e.g. it moves to the front from
+/// the ghost and to the ghost from the back.
+pub struct CursorMut<'a, T> {
+    list: &'a mut LinkedList<T>,
+    current: Link<T>,
+    /// The nodes around the current one, kept to hand out their elements
+    /// in the guards bound to the cursor.
+    prev: Link<T>,
+    next: Link<T>,
+    index: usize,
+}
+
+// keeps the list borrowed until the nodes are released.
+impl<T> Drop for CursorMut<'_, T> {
+    fn drop(&mut self) {}
+}
+
+impl<T: Debug> Debug for CursorMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut")
+            .field(&self.list)
+            .field(&self.index())
+            .finish()
     }
 }
 
-impl<T: Debug> DerefMut for Node<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.data
+impl<'a, T> CursorMut<'a, T> {
+    fn new(list: &'a mut LinkedList<T>, current: Link<T>, index: usize) -> Self {
+        let mut cursor = Self {
+            list,
+            current: None,
+            prev: None,
+            next: None,
+            index,
+        };
+        cursor.seek(current);
+        cursor
+    }
+
+    /// Returns the index of the element, or `None` at the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+
+    pub fn current(&mut self) -> Option<RefMut<'_, T>> {
+        self.current.as_deref().map(data_mut)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(_) => self.index += 1,
+            None => self.index = 0,
+        }
+        let next = self.next.clone();
+        self.seek(next);
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(_) => self.index = self.index.saturating_sub(1),
+            None => self.index = self.list.len.saturating_sub(1),
+        }
+        let prev = self.prev.clone();
+        self.seek(prev);
+    }
+
+    /// Returns the next element, or the front at the ghost.
+    pub fn peek_next(&mut self) -> Option<RefMut<'_, T>> {
+        self.next.as_deref().map(data_mut)
+    }
+
+    /// Returns the previous element, or the back at the ghost.
+    pub fn peek_prev(&mut self) -> Option<RefMut<'_, T>> {
+        self.prev.as_deref().map(data_mut)
+    }
+
+    /// Inserts the element after the current one, or at the front
+    /// at the ghost.
+    pub fn insert_after(&mut self, data: T) {
+        let current = match self.current.clone() {
+            Some(current) => current,
+            None => {
+                self.list.push_front(data);
+                return self.seek(None);
+            }
+        };
+        let node = Rc::new(RefCell::new(Node {
+            data,
+            next: self.next.clone(),
+            prev: Some(Rc::downgrade(&current)),
+        }));
+        match self.next {
+            Some(ref next) => next.borrow_mut().prev = Some(Rc::downgrade(&node)),
+            None => self.list.tail = Some(node.clone()),
+        }
+        current.borrow_mut().next = Some(node);
+        self.list.len += 1;
+        self.seek(Some(current));
+    }
+
+    /// Inserts the element before the current one, or at the back
+    /// at the ghost.
+    pub fn insert_before(&mut self, data: T) {
+        let current = match self.current.clone() {
+            Some(current) => current,
+            None => {
+                self.list.push_back(data);
+                return self.seek(None);
+            }
+        };
+        let node = Rc::new(RefCell::new(Node {
+            data,
+            next: Some(current.clone()),
+            prev: self.prev.as_ref().map(Rc::downgrade),
+        }));
+        current.borrow_mut().prev = Some(Rc::downgrade(&node));
+        match self.prev {
+            Some(ref prev) => prev.borrow_mut().next = Some(node),
+            None => self.list.head = Some(node),
+        }
+        self.list.len += 1;
+        self.index += 1;
+        self.seek(Some(current));
+    }
+
+    /// Removes the current element and moves to the next one, or returns
+    /// `None` at the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current.take()?;
+        let (prev, next) = (self.prev.take(), self.next.take());
+        {
+            let mut node = node.borrow_mut();
+            node.prev = None;
+            node.next = None;
+        }
+        match next {
+            Some(ref next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.list.tail = prev.clone(),
+        }
+        match prev {
+            Some(ref prev) => prev.borrow_mut().next = next.clone(),
+            None => self.list.head = next.clone(),
+        }
+        self.list.len -= 1;
+        self.seek(next);
+        Some(into_data(node))
+    }
+
+    /// Moves the cursor to the node, or to the ghost at `None`, along
+    /// with the nodes around it.
+    fn seek(&mut self, current: Link<T>) {
+        let (prev, next) = match current {
+            Some(ref node) => {
+                let node = node.borrow();
+                (
+                    node.prev.as_ref().and_then(Weak::upgrade),
+                    node.next.clone(),
+                )
+            }
+            None => (self.list.tail.clone(), self.list.head.clone()),
+        };
+        self.current = current;
+        self.prev = prev;
+        self.next = next;
     }
 }
 
-impl<T: Debug> From<T> for Node<T> {
+struct Node<T> {
+    data: T,
+    next: Link<T>,
+    prev: WeakLink<T>,
+}
+
+impl<T> From<T> for Node<T> {
     fn from(data: T) -> Self {
         Self {
             data,
@@ -156,3 +865,6 @@ impl<T: Debug> From<T> for Node<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod test;
@@ -11,7 +11,7 @@ fn main() {
     list.push_back("5".to_string());
     assert_eq!(list.len(), 5);
     for item in list.iter() {
-        println!("{}", **item.borrow());
+        println!("{}", item.borrow());
     }
     assert_eq!(list.pop_front(), Some(String::from("1")));
     assert_eq!(list.pop_back(), Some(String::from("5")));
@@ -0,0 +1,654 @@
+//! A doubly linked list on raw pointers
+//!
+//! It's the same list as [`std::collections::LinkedList`], of which nodes
+//! are boxed one by one and link each other by [`NonNull`] pointers.
+//! The list owns all the nodes, e.g. a node is only freed by the list
+//! after it's unlinked, and the borrows of the elements are bound to
+//! the borrow of the list, which keeps the safe API sound.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+
+/// A doubly linked list of the boxed nodes.
+pub struct LinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    /// Owns the nodes for the drop check and the variance.
+    _marker: PhantomData<Box<Node<T>>>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    data: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+// the list owns the elements, as `Box` does.
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        // pops one by one not to recurse on the long list.
+        while self.pop_front_node().is_some() {}
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord> Ord for LinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for data in self {
+            data.hash(state);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for LinkedList<T> {
+    fn from(array: [T; N]) -> Self {
+        Self::from_iter(array)
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.push_back(data);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> LinkedList<T> {
+    pub const fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn push_front(&mut self, data: T) {
+        let node = Box::new(Node {
+            data,
+            prev: None,
+            next: self.head,
+        });
+        let node = NonNull::from(Box::leak(node));
+        match self.head {
+            // SAFETY: the head is the live node owned by the list.
+            Some(head) => unsafe { (*head.as_ptr()).prev = Some(node) },
+            None => self.tail = Some(node),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, data: T) {
+        let node = Box::new(Node {
+            data,
+            prev: self.tail,
+            next: None,
+        });
+        let node = NonNull::from(Box::leak(node));
+        match self.tail {
+            // SAFETY: the tail is the live node owned by the list.
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.pop_front_node().map(|node| node.data)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|tail| {
+            // SAFETY: the tail was leaked from the box by the list, and
+            // nothing links to it once unlinked below.
+            let node = unsafe { Box::from_raw(tail.as_ptr()) };
+            self.tail = node.prev;
+            match self.tail {
+                // SAFETY: the previous node is still owned by the list.
+                Some(prev) => unsafe { (*prev.as_ptr()).next = None },
+                None => self.head = None,
+            }
+            self.len -= 1;
+            node.data
+        })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: the node lives while the list is borrowed.
+        self.head.map(|head| unsafe { &(*head.as_ptr()).data })
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: the node lives while the list is borrowed.
+        self.tail.map(|tail| unsafe { &(*tail.as_ptr()).data })
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the node lives while the list is borrowed mutably.
+        self.head.map(|head| unsafe { &mut (*head.as_ptr()).data })
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the node lives while the list is borrowed mutably.
+        self.tail.map(|tail| unsafe { &mut (*tail.as_ptr()).data })
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn contains(&self, data: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|x| x == data)
+    }
+
+    /// Moves all the elements of the `other` to the back of the list
+    /// in O(1).
+    pub fn append(&mut self, other: &mut Self) {
+        let other = mem::take(other);
+        match self.tail {
+            None => *self = other,
+            Some(tail) => {
+                if let Some(head) = other.head {
+                    // SAFETY: both nodes are live, and the nodes of
+                    // the other move into the list.
+                    unsafe {
+                        (*tail.as_ptr()).next = Some(head);
+                        (*head.as_ptr()).prev = Some(tail);
+                    }
+                    self.tail = other.tail;
+                    self.len += other.len;
+                    // the nodes are the list's now.
+                    mem::forget(other);
+                }
+            }
+        }
+    }
+
+    /// Splits the list into two at the index, and returns the elements
+    /// from the index on, walking from the closer end.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `at` is greater than the length.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split index (is {at}) should be <= len");
+        if at == 0 {
+            return mem::take(self);
+        }
+        if at == self.len {
+            return Self::new();
+        }
+        // finds the last node to keep.
+        let last = if at <= self.len / 2 {
+            let mut node = self.head;
+            for _ in 1..at {
+                // SAFETY: there are more than `at` live nodes.
+                node = unsafe { (*node.unwrap().as_ptr()).next };
+            }
+            node
+        } else {
+            let mut node = self.tail;
+            for _ in at..self.len {
+                // SAFETY: there are more than `len - at` live nodes.
+                node = unsafe { (*node.unwrap().as_ptr()).prev };
+            }
+            node
+        }
+        .unwrap();
+        // SAFETY: the node and the next one are live as `0 < at < len`.
+        let head = unsafe {
+            let head = (*last.as_ptr()).next.take().unwrap();
+            (*head.as_ptr()).prev = None;
+            head
+        };
+        let other = Self {
+            head: Some(head),
+            tail: self.tail.replace(last),
+            len: self.len - at,
+            _marker: PhantomData,
+        };
+        self.len = at;
+        other
+    }
+
+    /// Sorts the list in place in O(n log n), keeping the order of
+    /// the equal elements.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list with the comparator by the bottom-up merge sort,
+    /// which relinks the nodes without moving the elements.
+    ///
+    /// The nodes leak if the comparator panics, which leaves the list
+    /// empty.
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = mem::take(&mut self.len);
+        self.tail = None;
+        // the sorted runs of 2^i nodes linked by `next`, of which higher
+        // ones hold the earlier nodes.
+        let mut runs: Vec<Link<T>> = Vec::new();
+        let mut next = self.head.take();
+        while let Some(node) = next {
+            // SAFETY: the node is live, and cut off from the rest here.
+            next = unsafe { (*node.as_ptr()).next.take() };
+            let mut carry = Some(node);
+            for run in runs.iter_mut() {
+                match run.take() {
+                    Some(run) => carry = merge(Some(run), carry, &mut cmp),
+                    None => {
+                        *run = carry.take();
+                        break;
+                    }
+                }
+            }
+            if carry.is_some() {
+                runs.push(carry);
+            }
+        }
+        let mut sorted = None;
+        for run in runs {
+            sorted = merge(run, sorted, &mut cmp);
+        }
+        self.relink(sorted, len);
+    }
+
+    /// Removes the consecutive equal elements, e.g. all the duplicates
+    /// of the sorted list.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut current = self.head;
+        while let Some(node) = current {
+            // SAFETY: the nodes are live, and each duplicate is unlinked
+            // before it's freed.
+            unsafe {
+                while let Some(next) = (*node.as_ptr()).next {
+                    if (*next.as_ptr()).data != (*node.as_ptr()).data {
+                        break;
+                    }
+                    let dup = Box::from_raw(next.as_ptr());
+                    (*node.as_ptr()).next = dup.next;
+                    match dup.next {
+                        Some(after) => (*after.as_ptr()).prev = Some(node),
+                        None => self.tail = Some(node),
+                    }
+                    self.len -= 1;
+                    drop(dup);
+                }
+                current = (*node.as_ptr()).next;
+            }
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Makes the chain of `len` nodes linked by `next` the list, fixing
+    /// the `prev` links and the tail.
+    fn relink(&mut self, head: Link<T>, len: usize) {
+        let mut prev = None;
+        let mut next = head;
+        while let Some(node) = next {
+            // SAFETY: the nodes of the chain are live and owned by the list.
+            unsafe {
+                (*node.as_ptr()).prev = prev;
+                next = (*node.as_ptr()).next;
+            }
+            prev = Some(node);
+        }
+        self.head = head;
+        self.tail = prev;
+        self.len = len;
+    }
+
+    fn pop_front_node(&mut self) -> Option<Box<Node<T>>> {
+        self.head.map(|head| {
+            // SAFETY: the head was leaked from the box by the list, and
+            // nothing links to it once unlinked below.
+            let node = unsafe { Box::from_raw(head.as_ptr()) };
+            self.head = node.next;
+            match self.head {
+                // SAFETY: the next node is still owned by the list.
+                Some(next) => unsafe { (*next.as_ptr()).prev = None },
+                None => self.tail = None,
+            }
+            self.len -= 1;
+            node
+        })
+    }
+}
+
+/// Merges the two sorted lists into one sorted list by relinking their
+/// nodes, taking the element of `a` first for the equal ones.
+pub fn merge_sorted<T: Ord>(mut a: LinkedList<T>, mut b: LinkedList<T>) -> LinkedList<T> {
+    let len = a.len + b.len;
+    let head = merge(a.head.take(), b.head.take(), &mut T::cmp);
+    let mut list = LinkedList::new();
+    list.relink(head, len);
+    list
+}
+
+/// Merges the two sorted chains of the nodes linked by `next`.
+fn merge<T, F>(mut a: Link<T>, mut b: Link<T>, cmp: &mut F) -> Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut head = None;
+    let mut tail: Link<T> = None;
+    while let (Some(x), Some(y)) = (a, b) {
+        // SAFETY: the nodes of both chains are live, and only borrowed
+        // for the comparison.
+        let node = unsafe {
+            // takes the one of `a` unless `b` is less for the stability.
+            if cmp(&(*y.as_ptr()).data, &(*x.as_ptr()).data) == Ordering::Less {
+                b = (*y.as_ptr()).next;
+                y
+            } else {
+                a = (*x.as_ptr()).next;
+                x
+            }
+        };
+        match tail {
+            // SAFETY: the tail is the live node merged before.
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => head = Some(node),
+        }
+        tail = Some(node);
+    }
+    let rest = a.or(b);
+    match tail {
+        // SAFETY: the same as above.
+        Some(tail) => unsafe { (*tail.as_ptr()).next = rest },
+        None => head = rest,
+    }
+    head
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+/// An iterator over the elements of [`LinkedList`].
+pub struct Iter<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _marker: PhantomData<&'a Node<T>>,
+}
+
+// the iterator shares the elements, as `&T` does.
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|head| {
+            // SAFETY: the node lives while the list is borrowed, and
+            // the length keeps the iterator within the list.
+            let node = unsafe { &*head.as_ptr() };
+            self.head = node.next;
+            self.len -= 1;
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|tail| {
+            // SAFETY: the same as `next`.
+            let node = unsafe { &*tail.as_ptr() };
+            self.tail = node.prev;
+            self.len -= 1;
+            &node.data
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over the mutable elements of [`LinkedList`].
+pub struct IterMut<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _marker: PhantomData<&'a mut Node<T>>,
+}
+
+// the iterator lends the elements, as `&mut T` does.
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<T: Debug> Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let iter = Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        };
+        f.debug_list().entries(iter).finish()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|head| {
+            // SAFETY: the node lives while the list is borrowed mutably,
+            // and each element is lent only once as the length keeps
+            // the both ends from crossing.
+            unsafe {
+                self.head = (*head.as_ptr()).next;
+                self.len -= 1;
+                &mut (*head.as_ptr()).data
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|tail| {
+            // SAFETY: the same as `next`.
+            unsafe {
+                self.tail = (*tail.as_ptr()).prev;
+                self.len -= 1;
+                &mut (*tail.as_ptr()).data
+            }
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of [`LinkedList`].
+#[derive(Debug)]
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,232 @@
+use super::{merge_sorted, LinkedList};
+
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::thread;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Keeps the randomized tests short under Miri.
+const N: usize = if cfg!(miri) { 200 } else { 10_000 };
+
+#[test]
+fn test_push_pop() {
+    let mut list = LinkedList::new();
+    list.push_front(2);
+    list.push_front(1);
+    list.push_back(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.front(), Some(&1));
+    assert_eq!(list.back(), Some(&3));
+    *list.front_mut().unwrap() = 0;
+    *list.back_mut().unwrap() = 4;
+    assert!(list.contains(&2));
+    assert_eq!(list.pop_front(), Some(0));
+    assert_eq!(list.pop_back(), Some(4));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_back(), None);
+    assert_eq!(list.pop_front(), None);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn test_iter() {
+    let mut list = LinkedList::from([0, 1, 2, 3, 4]);
+    assert!(list.iter().eq(&[0, 1, 2, 3, 4]));
+    assert!(list.iter().rev().eq(&[4, 3, 2, 1, 0]));
+    let mut iter = list.iter();
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(format!("{iter:?}"), "[1, 2, 3]");
+
+    // takes the mutable references from both ends at once.
+    let mut iter = list.iter_mut();
+    let (first, last) = (iter.next().unwrap(), iter.next_back().unwrap());
+    *first += 10;
+    *last += 10;
+    for x in iter {
+        *x *= 10;
+    }
+    assert_eq!(format!("{list:?}"), "[10, 10, 20, 30, 14]");
+    assert!(list.into_iter().rev().eq([14, 30, 20, 10, 10]));
+}
+
+#[test]
+fn test_append_split_off() {
+    for at in 0..=6 {
+        let mut list = LinkedList::from([0, 1, 2]);
+        let mut other = LinkedList::from([3, 4, 5]);
+        list.append(&mut other);
+        assert!(other.is_empty());
+
+        let tail = list.split_off(at);
+        assert!(list.iter().copied().eq(0..at));
+        assert!(list.iter().rev().copied().eq((0..at).rev()));
+        assert!(tail.iter().copied().eq(at..6));
+        assert!(tail.iter().rev().copied().eq((at..6).rev()));
+        assert_eq!((list.len(), tail.len()), (at, 6 - at));
+    }
+
+    let mut empty = LinkedList::new();
+    empty.append(&mut LinkedList::from([7, 8]));
+    empty.append(&mut LinkedList::new());
+    assert_eq!(empty, LinkedList::from([7, 8]));
+}
+
+#[test]
+#[should_panic(expected = "split index")]
+fn test_split_off_out_of_bounds() {
+    LinkedList::from([0, 1, 2]).split_off(4);
+}
+
+#[test]
+fn test_traits() {
+    let list = LinkedList::from([1, 2, 3]);
+    assert_eq!(list.clone(), list);
+    assert!(list < LinkedList::from([1, 3]));
+    let mut extended = LinkedList::new();
+    extended.extend(&[1, 2]);
+    extended.extend([3]);
+    assert_eq!(extended, list);
+}
+
+#[test]
+fn test_drop_elements() {
+    let data = Rc::new(());
+    let mut list: LinkedList<_> = (0..10).map(|_| data.clone()).collect();
+    let tail = list.split_off(4);
+    let mut iter = tail.into_iter();
+    iter.next();
+    assert_eq!(Rc::strong_count(&data), 10);
+    drop(iter);
+    list.pop_back();
+    assert_eq!(Rc::strong_count(&data), 4);
+    drop(list);
+    assert_eq!(Rc::strong_count(&data), 1);
+}
+
+#[test]
+fn test_zero_sized() {
+    let mut list: LinkedList<()> = (0..10).map(|_| ()).collect();
+    assert_eq!(list.iter_mut().count(), 10);
+    assert_eq!(list.split_off(5).len(), 5);
+    assert_eq!(list.pop_front(), Some(()));
+    assert_eq!(list.len(), 4);
+}
+
+#[test]
+fn test_send() {
+    let mut list = LinkedList::from([1, 2, 3]);
+    thread::scope(|s| {
+        s.spawn(|| list.push_back(4));
+    });
+    let sum = thread::scope(|s| s.spawn(|| list.iter().sum::<i32>()).join().unwrap());
+    assert_eq!(sum, 10);
+}
+
+#[test]
+fn test_long_list_drop() {
+    let list: LinkedList<_> = (0..N * 10).collect();
+    assert_eq!(list.len(), N * 10);
+    drop(list);
+}
+
+#[test]
+fn test_against_vec_deque() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut list = LinkedList::new();
+    let mut oracle = VecDeque::new();
+
+    for i in 0..N {
+        match rng.gen_range(0..6) {
+            0 => {
+                list.push_front(i);
+                oracle.push_front(i);
+            }
+            1 => {
+                list.push_back(i);
+                oracle.push_back(i);
+            }
+            2 => assert_eq!(list.pop_front(), oracle.pop_front()),
+            3 => assert_eq!(list.pop_back(), oracle.pop_back()),
+            4 => {
+                let at = rng.gen_range(0..=oracle.len());
+                let mut tail = list.split_off(at);
+                let mut tail_oracle = oracle.split_off(at);
+                assert!(tail.iter().eq(tail_oracle.iter()));
+                tail.push_front(i);
+                tail_oracle.push_front(i);
+                list.append(&mut tail);
+                oracle.append(&mut tail_oracle);
+            }
+            _ => {
+                for (x, y) in list.iter_mut().zip(oracle.iter_mut()) {
+                    *x += 1;
+                    *y += 1;
+                }
+            }
+        }
+        assert_eq!(list.len(), oracle.len());
+        assert_eq!(list.front(), oracle.front());
+        assert_eq!(list.back(), oracle.back());
+    }
+    assert!(list.iter().eq(oracle.iter()));
+    assert!(list.iter().rev().eq(oracle.iter().rev()));
+}
+
+#[test]
+fn test_sort() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for n in [0, 1, 2, 3, 7, 64, N] {
+        let mut oracle: Vec<u8> = (0..n).map(|_| rng.gen()).collect();
+        let mut list: LinkedList<_> = oracle.iter().copied().collect();
+        list.sort();
+        oracle.sort();
+        assert_eq!(list.len(), n);
+        assert!(list.iter().eq(oracle.iter()));
+        assert!(list.iter().rev().eq(oracle.iter().rev()));
+    }
+
+    let mut oracle: Vec<(u8, usize)> = (0..N).map(|i| (rng.gen_range(0..10), i)).collect();
+    let mut list: LinkedList<_> = oracle.iter().copied().collect();
+    list.sort_by(|a, b| a.0.cmp(&b.0));
+    oracle.sort_by_key(|x| x.0);
+    assert!(list.iter().eq(oracle.iter()));
+    list.push_back((10, N));
+    assert_eq!(list.back(), Some(&(10, N)));
+}
+
+#[test]
+fn test_merge_sorted() {
+    let a = LinkedList::from([1, 3, 3, 5]);
+    let b = LinkedList::from([0, 3, 4, 6, 7]);
+    let mut list = merge_sorted(a, b);
+    assert_eq!(list.len(), 9);
+    assert_eq!(format!("{list:?}"), "[0, 1, 3, 3, 3, 4, 5, 6, 7]");
+    assert!(list.iter().rev().eq([7, 6, 5, 4, 3, 3, 3, 1, 0].iter()));
+    list.push_back(8);
+    let list = merge_sorted(list, LinkedList::new());
+    assert_eq!(list.len(), 10);
+    let list = merge_sorted(LinkedList::new(), list);
+    assert_eq!(list.len(), 10);
+    assert_eq!(list.back(), Some(&8));
+}
+
+#[test]
+fn test_dedup() {
+    let mut list = LinkedList::from([1, 1, 2, 3, 3, 3, 1, 4, 4]);
+    list.dedup();
+    assert_eq!(list.len(), 5);
+    assert_eq!(format!("{list:?}"), "[1, 2, 3, 1, 4]");
+    assert_eq!(list.back(), Some(&4));
+
+    // the removed elements are dropped.
+    let rc = Rc::new(());
+    let mut list: LinkedList<_> = (0..N * 3).map(|i| (i / 3, rc.clone())).collect();
+    list.dedup();
+    assert_eq!(list.len(), N);
+    assert_eq!(Rc::strong_count(&rc), list.len() + 1);
+    assert!(list.iter().map(|x| x.0).eq(0..N));
+}
@@ -0,0 +1,276 @@
+use super::{merge_sorted, LinkedList};
+
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Keeps the randomized tests short under Miri.
+const N: usize = if cfg!(miri) { 200 } else { 10_000 };
+
+#[test]
+fn test_push_pop() {
+    let mut list = LinkedList::new();
+    list.push_front(2);
+    list.push_front(1);
+    list.push_back(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.front().as_deref(), Some(&1));
+    assert_eq!(list.back().as_deref(), Some(&3));
+    *list.front_mut().unwrap() = 0;
+    *list.back_mut().unwrap() = 4;
+    assert_eq!(list.pop_front(), Some(0));
+    assert_eq!(list.pop_back(), Some(4));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_back(), None);
+    assert!(list.is_empty());
+    assert!(list.front().is_none());
+}
+
+#[test]
+fn test_iter() {
+    let mut list: LinkedList<_> = (0..5).collect();
+    assert!(list.iter().eq([0, 1, 2, 3, 4]));
+    assert!(list.iter().rev().eq([4, 3, 2, 1, 0]));
+    let mut iter = list.iter();
+    assert_eq!(iter.next().unwrap(), 0);
+    assert_eq!(iter.next_back().unwrap(), 4);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(format!("{iter:?}"), "[1, 2, 3]");
+    drop(iter);
+
+    for mut x in &mut list {
+        *x.borrow_mut() *= 10;
+    }
+    assert_eq!(format!("{list:?}"), "[0, 10, 20, 30, 40]");
+    assert_eq!(list.iter_mut().next_back().unwrap(), 40);
+    assert!(list.into_iter().rev().eq([40, 30, 20, 10, 0]));
+}
+
+#[test]
+fn test_append_split_off() {
+    let mut list: LinkedList<_> = (0..3).collect();
+    let mut other: LinkedList<_> = (3..6).collect();
+    list.append(&mut other);
+    assert!(other.is_empty());
+    assert!(list.iter().eq(0..6));
+
+    let tail = list.split_off(4);
+    assert!(list.iter().eq(0..4));
+    assert!(tail.iter().eq(4..6));
+    assert!(list.split_off(4).is_empty());
+    assert_eq!(list.split_off(0).len(), 4);
+    assert!(list.is_empty());
+
+    let mut empty = LinkedList::new();
+    empty.append(&mut (7..9).collect());
+    assert_eq!(empty, (7..9).collect());
+}
+
+#[test]
+#[should_panic(expected = "split index")]
+fn test_split_off_out_of_bounds() {
+    let mut list: LinkedList<_> = (0..3).collect();
+    list.split_off(4);
+}
+
+#[test]
+fn test_sort() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for n in [0, 1, 2, 3, 7, 64, 1_000] {
+        let mut oracle: Vec<u8> = (0..n).map(|_| rng.gen()).collect();
+        let mut list: LinkedList<_> = oracle.iter().copied().collect();
+        list.sort();
+        oracle.sort();
+        assert_eq!(list.len(), n);
+        assert!(list.iter().eq(oracle.iter().copied()));
+        assert!(list.iter().rev().eq(oracle.iter().rev().copied()));
+    }
+}
+
+#[test]
+fn test_sort_stable() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut oracle: Vec<(u8, usize)> = (0..1_000).map(|i| (rng.gen_range(0..10), i)).collect();
+    let mut list: LinkedList<_> = oracle.iter().copied().collect();
+    list.sort_by(|a, b| a.0.cmp(&b.0));
+    oracle.sort_by_key(|x| x.0);
+    assert!(list.iter().eq(oracle.iter().copied()));
+}
+
+#[test]
+fn test_sort_relinks() {
+    let mut list: LinkedList<_> = [3, 1, 2].into_iter().collect();
+    list.sort();
+    assert_eq!(list.back().as_deref(), Some(&3));
+    list.push_back(4);
+    list.push_front(0);
+    assert_eq!(format!("{list:?}"), "[0, 1, 2, 3, 4]");
+    assert!(list.iter().rev().eq([4, 3, 2, 1, 0]));
+}
+
+#[test]
+fn test_sort_panic() {
+    let mut list: LinkedList<_> = (0..10).collect();
+    let sorted = panic::catch_unwind(AssertUnwindSafe(|| {
+        list.sort_by(|a, b| {
+            assert!(*a != 5 && *b != 5, "compared 5");
+            a.cmp(b)
+        })
+    }));
+    assert!(sorted.is_err());
+    // the list is left empty but sound.
+    assert_eq!(list.len(), 0);
+    assert!(list.back().is_none());
+    assert_eq!(list.iter().rev().count(), 0);
+    list.push_back(1);
+    assert!(list.iter().rev().eq([1]));
+
+    #[derive(Debug)]
+    struct Bad(i32);
+
+    impl PartialEq for Bad {
+        fn eq(&self, other: &Self) -> bool {
+            assert!(self.0 != 3, "compared 3");
+            self.0 == other.0
+        }
+    }
+
+    let mut list: LinkedList<_> = [1, 1, 2, 3, 3].into_iter().map(Bad).collect();
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| list.dedup())).is_err());
+    assert_eq!(list.len(), 0);
+    assert!(list.back().is_none());
+    assert_eq!(list.iter().rev().count(), 0);
+}
+
+#[test]
+fn test_merge_sorted() {
+    let a: LinkedList<_> = [1, 3, 3, 5].into_iter().collect();
+    let b: LinkedList<_> = [0, 3, 4, 6, 7].into_iter().collect();
+    let mut list = merge_sorted(a, b);
+    assert_eq!(list.len(), 9);
+    assert_eq!(format!("{list:?}"), "[0, 1, 3, 3, 3, 4, 5, 6, 7]");
+    assert!(list.iter().rev().eq([7, 6, 5, 4, 3, 3, 3, 1, 0]));
+    assert_eq!(list.back().as_deref(), Some(&7));
+    list.push_back(8);
+    let list = merge_sorted(list, LinkedList::new());
+    assert_eq!(list.len(), 10);
+    let list = merge_sorted(LinkedList::new(), list);
+    assert_eq!(list.len(), 10);
+    assert_eq!(list.back().as_deref(), Some(&8));
+}
+
+#[test]
+fn test_dedup() {
+    let mut list: LinkedList<_> = [1, 1, 2, 3, 3, 3, 1, 4, 4].into_iter().collect();
+    list.dedup();
+    assert_eq!(list.len(), 5);
+    assert_eq!(format!("{list:?}"), "[1, 2, 3, 1, 4]");
+    assert_eq!(list.back().as_deref(), Some(&4));
+
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut oracle: Vec<u8> = (0..1_000).map(|_| rng.gen_range(0..50)).collect();
+    let mut list: LinkedList<_> = oracle.iter().copied().collect();
+    list.sort();
+    list.dedup();
+    oracle.sort();
+    oracle.dedup();
+    assert_eq!(list.len(), oracle.len());
+    assert!(list.iter().eq(oracle.iter().copied()));
+    assert!(list.iter().rev().eq(oracle.iter().rev().copied()));
+}
+
+#[test]
+fn test_cursor() {
+    let mut list: LinkedList<_> = (1..4).collect();
+    let mut cursor = list.cursor_front_mut();
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(cursor.current().as_deref(), Some(&1));
+    cursor.move_next();
+    assert_eq!(cursor.peek_prev().as_deref(), Some(&1));
+    assert_eq!(cursor.peek_next().as_deref(), Some(&3));
+    cursor.insert_before(10);
+    cursor.insert_after(20);
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(cursor.remove_current(), Some(2));
+    assert_eq!(cursor.current().as_deref(), Some(&20));
+    cursor.move_next();
+    cursor.move_next();
+    // at the ghost between the back and the front.
+    assert_eq!(cursor.index(), None);
+    assert!(cursor.current().is_none());
+    assert_eq!(cursor.remove_current(), None);
+    assert_eq!(cursor.peek_next().as_deref(), Some(&1));
+    assert_eq!(cursor.peek_prev().as_deref(), Some(&3));
+    cursor.insert_after(0);
+    cursor.insert_before(4);
+    cursor.move_prev();
+    assert_eq!(cursor.current().as_deref(), Some(&4));
+    assert_eq!(cursor.index(), Some(5));
+    drop(cursor);
+    assert!(list.iter().eq([0, 1, 10, 20, 3, 4]));
+
+    let mut cursor = list.cursor_back_mut();
+    assert_eq!(cursor.remove_current(), Some(4));
+    assert_eq!(cursor.index(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.current().as_deref(), Some(&3));
+    assert_eq!(cursor.index(), Some(4));
+    drop(cursor);
+    assert!(list.iter().eq([0, 1, 10, 20, 3]));
+}
+
+#[test]
+fn test_against_vec_deque() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut list = LinkedList::new();
+    let mut oracle = VecDeque::new();
+
+    for i in 0..N {
+        match rng.gen_range(0..6) {
+            0 => {
+                list.push_front(i);
+                oracle.push_front(i);
+            }
+            1 => {
+                list.push_back(i);
+                oracle.push_back(i);
+            }
+            2 => assert_eq!(list.pop_front(), oracle.pop_front()),
+            3 => assert_eq!(list.pop_back(), oracle.pop_back()),
+            _ => {
+                // edits around the random position with the cursor.
+                let at = rng.gen_range(0..=oracle.len());
+                let mut cursor = list.cursor_front_mut();
+                for _ in 0..at {
+                    cursor.move_next();
+                }
+                if rng.gen_bool(0.5) {
+                    cursor.insert_before(i);
+                    oracle.insert(at, i);
+                } else {
+                    let removed = cursor.remove_current();
+                    let want = if at < oracle.len() {
+                        oracle.remove(at)
+                    } else {
+                        None
+                    };
+                    assert_eq!(removed, want);
+                }
+            }
+        }
+        assert_eq!(list.len(), oracle.len());
+        assert_eq!(list.front().as_deref(), oracle.front());
+        assert_eq!(list.back().as_deref(), oracle.back());
+    }
+    assert!(list.iter().eq(oracle.iter().copied()));
+    assert!(list.iter().rev().eq(oracle.iter().rev().copied()));
+}
+
+#[test]
+fn test_long_list_drop() {
+    let list: LinkedList<_> = (0..N * 10).collect();
+    assert_eq!(list.len(), N * 10);
+    drop(list);
+}
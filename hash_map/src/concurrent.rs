@@ -0,0 +1,122 @@
+//! A concurrent sharded hash map
+
+use std::borrow::{Borrow, BorrowMut};
+use std::hash::Hash;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::HMap;
+
+// Default number of shards.
+const NSHARDS: usize = 16;
+
+/// A hash map shared across threads.
+///
+/// Keys are spread over the shards by the lower bits of `hasher::hash`,
+/// and each shard is an [`HMap`] behind its own lock, so that threads
+/// working on different shards don't wait for each other.  Each shard
+/// grows its buckets incrementally on its own.
+#[derive(Debug)]
+pub struct ConcurrentHMap<K, V> {
+    seed: u64,
+    mask: usize,
+    shards: Vec<Mutex<HMap<K, V>>>,
+}
+
+impl<K, V> Default for ConcurrentHMap<K, V> {
+    fn default() -> Self {
+        Self::with_shards(NSHARDS)
+    }
+}
+
+impl<K, V> ConcurrentHMap<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a map with at least `n` shards.
+    ///
+    /// The number of shards is rounded up to the next power of two.
+    pub fn with_shards(n: usize) -> Self {
+        let n = n.max(1).next_power_of_two();
+        Self {
+            seed: rand::random(),
+            mask: n - 1,
+            shards: (0..n).map(|_| Mutex::new(HMap::default())).collect(),
+        }
+    }
+
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+}
+
+impl<K: Eq + Hash, V> ConcurrentHMap<K, V> {
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of entries.
+    ///
+    /// Shards are counted one at a time, so the result is only a
+    /// snapshot while other threads are updating the map.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().len())
+            .sum()
+    }
+
+    pub fn insert(&self, k: K, v: V) {
+        self.shard(&k).insert(k, v);
+    }
+
+    /// Returns the clone of the value, as the reference can't
+    /// outlive the shard lock.
+    pub fn get_cloned<Q>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        V: Clone,
+    {
+        self.shard(k).get(k).cloned()
+    }
+
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.shard(k).get(k).is_some()
+    }
+
+    pub fn remove<Q>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.shard(k).remove(k)
+    }
+
+    /// Updates the value in place with `f` while holding the shard lock.
+    ///
+    /// It returns `None` in case there is no matched key.
+    pub fn update<Q, F, R>(&self, k: &Q, f: F) -> Option<R>
+    where
+        K: BorrowMut<Q>,
+        Q: Eq + Hash + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        self.shard(k).get_mut(k).map(f)
+    }
+
+    fn shard<Q>(&self, k: &Q) -> MutexGuard<'_, HMap<K, V>>
+    where
+        Q: Hash + ?Sized,
+    {
+        let h = hasher::hash(self.seed, k) as usize & self.mask;
+        self.shards[h].lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,111 @@
+use super::ConcurrentHMap;
+
+use std::thread;
+
+#[test]
+fn test_shards_power_of_two() {
+    let map = ConcurrentHMap::<u32, u32>::with_shards(5);
+    assert_eq!(map.shards(), 8);
+    let map = ConcurrentHMap::<u32, u32>::with_shards(0);
+    assert_eq!(map.shards(), 1);
+}
+
+#[test]
+fn test_insert_get_remove() {
+    let map = ConcurrentHMap::new();
+    map.insert("keith".to_string(), 18);
+    map.insert("dave".to_string(), 1);
+    map.insert("andy".to_string(), 9);
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get_cloned("dave"), Some(1));
+    assert_eq!(map.remove("dave"), Some(1));
+    assert_eq!(map.remove("dave"), None);
+    assert_eq!(map.get_cloned("dave"), None);
+    assert!(map.contains_key("keith"));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_update() {
+    let map = ConcurrentHMap::new();
+    map.insert("keith".to_string(), 18);
+
+    assert_eq!(map.update("keith", |v| *v += 1), Some(()));
+    assert_eq!(map.get_cloned("keith"), Some(19));
+    assert_eq!(map.update("andy", |v| *v += 1), None);
+}
+
+#[test]
+fn test_lots_of_numbers_across_threads() {
+    const THREADS: usize = 8;
+    const N: usize = 5_000;
+
+    let map = ConcurrentHMap::new();
+    thread::scope(|s| {
+        for t in 0..THREADS {
+            let map = &map;
+            s.spawn(move || {
+                for x in (t * N)..((t + 1) * N) {
+                    map.insert(x, x + 250);
+                }
+            });
+        }
+    });
+
+    assert_eq!(map.len(), THREADS * N);
+    for x in 0..THREADS * N {
+        assert_eq!(map.get_cloned(&x), Some(x + 250));
+    }
+
+    // removes the even keys while the other threads are reading
+    // the odd ones.
+    thread::scope(|s| {
+        for t in 0..THREADS {
+            let map = &map;
+            s.spawn(move || {
+                for x in (t * N)..((t + 1) * N) {
+                    if x % 2 == 0 {
+                        assert_eq!(map.remove(&x), Some(x + 250));
+                    } else {
+                        assert_eq!(map.get_cloned(&x), Some(x + 250));
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(map.len(), THREADS * N / 2);
+    for x in 0..THREADS * N {
+        let want = if x % 2 == 0 { None } else { Some(x + 250) };
+        assert_eq!(map.get_cloned(&x), want);
+    }
+}
+
+#[test]
+fn test_concurrent_updates_on_same_keys() {
+    const THREADS: usize = 8;
+    const KEYS: usize = 64;
+    const ROUNDS: usize = 1_000;
+
+    let map = ConcurrentHMap::with_shards(4);
+    for k in 0..KEYS {
+        map.insert(k, 0);
+    }
+    thread::scope(|s| {
+        for _ in 0..THREADS {
+            let map = &map;
+            s.spawn(move || {
+                for _ in 0..ROUNDS {
+                    for k in 0..KEYS {
+                        map.update(&k, |v| *v += 1).unwrap();
+                    }
+                }
+            });
+        }
+    });
+
+    for k in 0..KEYS {
+        assert_eq!(map.get_cloned(&k), Some(THREADS * ROUNDS));
+    }
+}
@@ -7,12 +7,26 @@ use std::hash::Hash;
 
 use bucket_list::BucketList;
 
+pub use concurrent::ConcurrentHMap;
+
+mod concurrent;
+
 // Each bucket size before increase the total number of buckets.
 const BSIZE: usize = 8;
 
 // Incremental number of increased buckets.
 const BGROW: usize = 4;
 
+/// A hash map occupancy statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    /// The number of main buckets moved to the grow buckets.
+    pub n_moved: usize,
+    pub main: bucket_list::Stats,
+    /// Only meaningful while moving the buckets, e.g. `n_moved > 0`.
+    pub grow: bucket_list::Stats,
+}
+
 #[derive(Debug)]
 pub struct HMap<K, V> {
     n_moved: usize,
@@ -30,6 +44,18 @@ impl<K, V> Default for HMap<K, V> {
     }
 }
 
+impl<K, V> HMap<K, V> {
+    /// Creates a hash map with the explicit hash seed, so that
+    /// the bucket layout is reproducible.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            n_moved: 0,
+            main: BucketList::with_seed(seed),
+            grow: BucketList::with_seed(seed),
+        }
+    }
+}
+
 impl<K: Eq + Hash, V> HMap<K, V> {
     pub fn new() -> Self {
         Self::default()
@@ -72,6 +98,15 @@ impl<K: Eq + Hash, V> HMap<K, V> {
         self.main.get_mut(k).or_else(|| self.grow.get_mut(k))
     }
 
+    /// Removes the key and returns the value if it was in the map.
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.main.remove(k).or_else(|| self.grow.remove(k))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -80,6 +115,20 @@ impl<K: Eq + Hash, V> HMap<K, V> {
         self.main.len() + self.grow.len()
     }
 
+    /// Returns the bucket occupancy statistics.
+    pub fn stats(&self) -> Stats {
+        Stats {
+            n_moved: self.n_moved,
+            main: self.main.stats(),
+            grow: self.grow.stats(),
+        }
+    }
+
+    /// Returns the iterator over the key/value pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.main.iter().chain(self.grow.iter())
+    }
+
     fn move_bucket(&mut self) {
         if self.n_moved == 0 {
             self.grow.set_buckets(self.main.len() + BGROW);
@@ -97,5 +146,7 @@ impl<K: Eq + Hash, V> HMap<K, V> {
     }
 }
 
+#[cfg(feature = "serde")]
+mod serde_impl;
 #[cfg(test)]
 mod test;
@@ -46,7 +46,7 @@ fn test_map_len() {
 
 #[test]
 fn test_lots_of_numbers() {
-    let mut map = HMap::new();
+    let mut map = HMap::with_seed(55);
     for x in 0..10_000 {
         map.insert(x, x + 250);
     }
@@ -54,10 +54,76 @@ fn test_lots_of_numbers() {
     assert_eq!(map.len(), 10_000);
     assert_eq!(map.get(&500), Some(&750));
 
-    for (i, b) in map.main.buckets.iter().enumerate() {
-        assert!(b.len() < 10, "main bucket[{i}] too big: {}", b.len());
+    let stats = map.stats();
+    assert!(
+        stats.main.longest < 10,
+        "main bucket too big: {:?}",
+        stats.main
+    );
+    assert!(
+        stats.grow.longest < 10,
+        "grow bucket too big: {:?}",
+        stats.grow
+    );
+}
+
+#[test]
+fn test_remove() {
+    let mut map = HMap::new();
+    for x in 0..1_000 {
+        map.insert(x, x + 250);
     }
-    for (i, b) in map.grow.buckets.iter().enumerate() {
-        assert!(b.len() < 10, "grow bucket[{i}] too big: {}", b.len());
+
+    for x in (0..1_000).step_by(2) {
+        assert_eq!(map.remove(&x), Some(x + 250));
+    }
+    assert_eq!(map.remove(&0), None);
+    assert_eq!(map.len(), 500);
+    for x in 0..1_000 {
+        let want = if x % 2 == 0 { None } else { Some(&(x + 250)) };
+        assert_eq!(map.get(&x), want);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip_during_migration() {
+    let mut map = HMap::with_seed(55);
+    let mut x = 0;
+    // stops in the middle of the bucket migration.
+    while map.n_moved == 0 || x < 100 {
+        map.insert(x, x + 250);
+        x += 1;
+    }
+
+    let data = bincode::serialize(&map).unwrap();
+    let got: HMap<i32, i32> = bincode::deserialize(&data).unwrap();
+    assert_eq!(got.len(), x as usize);
+    for i in 0..x {
+        assert_eq!(got.get(&i), Some(&(i + 250)));
+    }
+
+    // The serialized form is just a map.
+    let got: std::collections::BTreeMap<i32, i32> = bincode::deserialize(&data).unwrap();
+    let want: std::collections::BTreeMap<i32, i32> = (0..x).map(|i| (i, i + 250)).collect();
+    assert_eq!(got, want);
+}
+
+#[test]
+fn test_stats_during_migration() {
+    let mut map = HMap::with_seed(55);
+    assert_eq!(map.stats().n_moved, 0);
+
+    let mut x = 0;
+    while map.stats().n_moved == 0 {
+        map.insert(x, x);
+        x += 1;
     }
+    let stats = map.stats();
+    assert_eq!(stats.main.len + stats.grow.len, map.len());
+    assert!(stats.grow.buckets > stats.main.buckets);
+    assert_eq!(
+        stats.main.histogram.iter().sum::<usize>(),
+        stats.main.buckets
+    );
 }
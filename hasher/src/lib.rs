@@ -22,8 +22,8 @@ struct MHash {
 impl Hasher for MHash {
     fn write(&mut self, data: &[u8]) {
         for d in data {
-            self.n = ((self.n + 11) * (*d as u128 + 13) + (*d ^ self.prev) as u128)
-                % (std::u64::MAX as u128);
+            self.n =
+                ((self.n + 11) * (*d as u128 + 13) + (*d ^ self.prev) as u128) % (u64::MAX as u128);
             self.prev = *d;
         }
     }
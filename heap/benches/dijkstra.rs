@@ -0,0 +1,133 @@
+//! Benchmarks the heaps on Dijkstra's algorithm over a random graph,
+//! both by pushing the duplicate entries through `PriorityQueue` and by
+//! decreasing the keys through `MergeableHeap`.
+//!
+//! It doesn't meld, which takes O(m) of the other heap for both the pairing
+//! and the Fibonacci heaps as they move its nodes into their arenas, rather
+//! than O(1) of the textbook heaps on the pointers.
+
+use std::cmp::Reverse;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use heap::{FibonacciHeap, IndexedHeap, MergeableHeap, MinHeap, PairingHeap, PriorityQueue};
+
+const NODES: usize = 10_000;
+
+// Edges per node.
+const DEGREE: usize = 8;
+
+/// The adjacency lists of (node, weight).
+type Graph = Vec<Vec<(usize, u64)>>;
+
+fn graph() -> Graph {
+    let mut rng = StdRng::seed_from_u64(55);
+    (0..NODES)
+        .map(|_| {
+            (0..DEGREE)
+                .map(|_| (rng.gen_range(0..NODES), rng.gen_range(1..1_000)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Finds the distances by pushing the node again for the shorter
+/// distance, and skipping the visited ones on the pop.
+fn lazy<Q: PriorityQueue<(u64, usize)>>(graph: &Graph, mut queue: Q) -> Vec<u64> {
+    let mut dists = vec![u64::MAX; graph.len()];
+    dists[0] = 0;
+    queue.push((0, 0));
+    while let Some((dist, node)) = queue.pop() {
+        if dist > dists[node] {
+            continue;
+        }
+        for &(next, weight) in &graph[node] {
+            if dist + weight < dists[next] {
+                dists[next] = dist + weight;
+                queue.push((dist + weight, next));
+            }
+        }
+    }
+    dists
+}
+
+/// Finds the distances by decreasing the key of each node in the heap.
+fn decrease_key<H: MergeableHeap<(u64, usize)> + Default>(graph: &Graph) -> Vec<u64> {
+    let mut heap = H::default();
+    let mut dists = vec![u64::MAX; graph.len()];
+    let mut handles = vec![None; graph.len()];
+    dists[0] = 0;
+    handles[0] = Some(heap.insert((0, 0)));
+    while let Some((dist, node)) = heap.pop() {
+        handles[node] = None;
+        for &(next, weight) in &graph[node] {
+            if dist + weight < dists[next] {
+                dists[next] = dist + weight;
+                match handles[next] {
+                    Some(handle) => heap.decrease_key(handle, (dist + weight, next)),
+                    None => handles[next] = Some(heap.insert((dist + weight, next))),
+                }
+            }
+        }
+    }
+    dists
+}
+
+/// Finds the distances by changing the priority of the node by the key.
+fn indexed(graph: &Graph, arity: usize) -> Vec<u64> {
+    let mut heap = IndexedHeap::new(arity);
+    let mut dists = vec![u64::MAX; graph.len()];
+    dists[0] = 0;
+    heap.push(0, Reverse(0));
+    while let Some((node, Reverse(dist))) = heap.pop() {
+        for &(next, weight) in &graph[node] {
+            if dist + weight < dists[next] {
+                dists[next] = dist + weight;
+                heap.push(next, Reverse(dist + weight));
+            }
+        }
+    }
+    dists
+}
+
+fn dijkstra(c: &mut Criterion) {
+    let graph = graph();
+    let want = lazy(&graph, MinHeap::new(2));
+    assert_eq!(lazy(&graph, PairingHeap::new()), want);
+    assert_eq!(lazy(&graph, FibonacciHeap::new()), want);
+    assert_eq!(decrease_key::<PairingHeap<_>>(&graph), want);
+    assert_eq!(decrease_key::<FibonacciHeap<_>>(&graph), want);
+    assert_eq!(indexed(&graph, 4), want);
+
+    let mut bench = |id: BenchmarkId, f: &dyn Fn(&Graph) -> Vec<u64>| {
+        c.bench_with_input(id, &graph, |b, graph| b.iter(|| black_box(f(graph))));
+    };
+    for arity in [2, 4] {
+        bench(BenchmarkId::new("lazy/heap", arity), &|graph| {
+            lazy(graph, MinHeap::new(arity))
+        });
+        bench(
+            BenchmarkId::new("decrease_key/indexed_heap", arity),
+            &|graph| indexed(graph, arity),
+        );
+    }
+    bench(BenchmarkId::new("lazy", "pairing_heap"), &|graph| {
+        lazy(graph, PairingHeap::new())
+    });
+    bench(BenchmarkId::new("lazy", "fibonacci_heap"), &|graph| {
+        lazy(graph, FibonacciHeap::new())
+    });
+    bench(
+        BenchmarkId::new("decrease_key", "pairing_heap"),
+        &decrease_key::<PairingHeap<_>>,
+    );
+    bench(
+        BenchmarkId::new("decrease_key", "fibonacci_heap"),
+        &decrease_key::<FibonacciHeap<_>>,
+    );
+}
+
+criterion_group!(benches, dijkstra);
+criterion_main!(benches);
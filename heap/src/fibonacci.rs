@@ -0,0 +1,368 @@
+//! A Fibonacci heap
+//!
+//! The heap is a circular list of the trees, of which children are also
+//! in the circular lists, and the nodes live in the arena and link each
+//! other by the index.  The insert adds a tree to the list in O(1), and
+//! the decrease-key cuts the node off to the list in amortized O(1) with
+//! the cascading cuts of the marked parents.  The pop consolidates
+//! the trees of the same degree in amortized O(log n).  The meld splices
+//! the root lists in O(1), but only after it moves the nodes of the other
+//! heap into the arena in O(m).
+
+use std::mem;
+
+use crate::{next_generation, Handle, MergeableHeap, PriorityQueue};
+
+// The null link.
+const NIL: usize = usize::MAX;
+
+/// A min heap based on the Fibonacci heap.
+#[derive(Debug)]
+pub struct FibonacciHeap<T> {
+    nodes: Vec<Node<T>>,
+    /// The popped nodes to be reused.
+    free: Vec<usize>,
+    /// The least root, e.g. any root of the list.
+    min: usize,
+    len: usize,
+}
+
+#[derive(Debug)]
+struct Node<T> {
+    /// The value, or `None` once popped.
+    value: Option<T>,
+    /// Tells the node from the popped one of the same index.
+    generation: u64,
+    parent: usize,
+    /// Any child.
+    child: usize,
+    /// The siblings in the circular list.
+    left: usize,
+    right: usize,
+    /// The number of the children.
+    degree: usize,
+    /// Whether the node has lost a child since it became a child.
+    marked: bool,
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for FibonacciHeap<T> {
+    fn push(&mut self, value: T) {
+        self.insert(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        FibonacciHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        FibonacciHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for FibonacciHeap<T> {
+    fn insert(&mut self, value: T) -> Handle {
+        FibonacciHeap::insert(self, value)
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        FibonacciHeap::get(self, handle)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, value: T) {
+        FibonacciHeap::decrease_key(self, handle, value);
+    }
+
+    fn meld(&mut self, other: Self) {
+        FibonacciHeap::meld(self, other);
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            min: NIL,
+            len: 0,
+        }
+    }
+
+    /// Inserts the value in O(1), and returns the handle of it.
+    pub fn insert(&mut self, value: T) -> Handle {
+        let node = self.alloc(value);
+        self.add_root(node);
+        self.len += 1;
+        self.handle(node)
+    }
+
+    /// Pops the least value in amortized O(log n).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.min == NIL {
+            return None;
+        }
+        let min = self.min;
+        // moves the children up to the roots.
+        let mut child = mem::replace(&mut self.nodes[min].child, NIL);
+        while child != NIL {
+            let next = match self.nodes[child].right {
+                right if right == child => NIL,
+                right => right,
+            };
+            self.remove_sibling(child);
+            self.nodes[child].parent = NIL;
+            self.nodes[child].marked = false;
+            self.splice(min, child);
+            child = next;
+        }
+        self.min = match self.nodes[min].right {
+            right if right == min => NIL,
+            right => right,
+        };
+        self.remove_sibling(min);
+        if self.min != NIL {
+            self.consolidate();
+        }
+        self.free.push(min);
+        self.len -= 1;
+        self.nodes[min].value.take()
+    }
+
+    /// Returns the least value.
+    pub fn peek(&self) -> Option<&T> {
+        match self.min {
+            NIL => None,
+            min => self.nodes[min].value.as_ref(),
+        }
+    }
+
+    /// Returns the value of the handle, or `None` once it's popped.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes
+            .get(handle.index)
+            .filter(|node| node.generation == handle.generation)?
+            .value
+            .as_ref()
+    }
+
+    /// Decreases the value of the handle in amortized O(1).
+    ///
+    /// # Panics
+    ///
+    /// It panics when the handle is popped or the value is greater than
+    /// the current one.
+    pub fn decrease_key(&mut self, handle: Handle, value: T) {
+        let node = handle.index;
+        let current = self.get(handle).expect("popped handle");
+        assert!(value <= *current, "greater value");
+        self.nodes[node].value = Some(value);
+        let mut parent = self.nodes[node].parent;
+        if parent != NIL && self.value(node) < self.value(parent) {
+            self.cut(node);
+            // cuts the marked ancestors, and marks the first unmarked one.
+            while self.nodes[parent].parent != NIL {
+                if !self.nodes[parent].marked {
+                    self.nodes[parent].marked = true;
+                    break;
+                }
+                let grandparent = self.nodes[parent].parent;
+                self.cut(parent);
+                parent = grandparent;
+            }
+        }
+        if self.value(node) < self.value(self.min) {
+            self.min = node;
+        }
+    }
+
+    /// Moves all the values of the `other` into the heap.
+    ///
+    /// It splices the root lists in O(1) after moving the nodes of
+    /// the `other` into the arena of the heap in O(m) without any
+    /// comparison but one, and the handles of the `other` are no longer
+    /// valid unless the heap is empty.
+    pub fn meld(&mut self, mut other: Self) {
+        if self.len == 0 {
+            mem::swap(self, &mut other);
+            return;
+        }
+        if other.len == 0 {
+            return;
+        }
+        let offset = self.nodes.len();
+        let shift = |link: usize| if link == NIL { NIL } else { link + offset };
+        self.nodes.extend(other.nodes.drain(..).map(|node| Node {
+            parent: shift(node.parent),
+            child: shift(node.child),
+            left: shift(node.left),
+            right: shift(node.right),
+            ..node
+        }));
+        self.free.extend(other.free.drain(..).map(shift));
+        // joins the two circular lists after the two minimums.
+        let (a, b) = (self.min, shift(other.min));
+        let (a_right, b_right) = (self.nodes[a].right, self.nodes[b].right);
+        self.nodes[a].right = b_right;
+        self.nodes[b_right].left = a;
+        self.nodes[b].right = a_right;
+        self.nodes[a_right].left = b;
+        if self.value(b) < self.value(a) {
+            self.min = b;
+        }
+        self.len += other.len;
+    }
+
+    /// Links the roots of the same degree until all the degrees differ,
+    /// and finds the new minimum.
+    fn consolidate(&mut self) {
+        let mut roots = vec![self.min];
+        let mut next = self.nodes[self.min].right;
+        while next != self.min {
+            roots.push(next);
+            next = self.nodes[next].right;
+        }
+        let mut degrees: Vec<usize> = Vec::new();
+        for mut root in roots {
+            loop {
+                let degree = self.nodes[root].degree;
+                if degree >= degrees.len() {
+                    degrees.resize(degree + 1, NIL);
+                }
+                let other = mem::replace(&mut degrees[degree], NIL);
+                if other == NIL {
+                    degrees[degree] = root;
+                    break;
+                }
+                root = if self.value(other) < self.value(root) {
+                    self.link(other, root)
+                } else {
+                    self.link(root, other)
+                };
+            }
+        }
+        self.min = NIL;
+        for root in degrees.into_iter().filter(|&root| root != NIL) {
+            if self.min == NIL || self.value(root) < self.value(self.min) {
+                self.min = root;
+            }
+        }
+    }
+
+    /// Makes the `child` root a child of the `parent` root.
+    fn link(&mut self, parent: usize, child: usize) -> usize {
+        self.remove_sibling(child);
+        match self.nodes[parent].child {
+            NIL => self.nodes[parent].child = child,
+            first => self.splice(first, child),
+        }
+        self.nodes[child].parent = parent;
+        self.nodes[child].marked = false;
+        self.nodes[parent].degree += 1;
+        parent
+    }
+
+    /// Cuts the node off from its parent to the roots.
+    fn cut(&mut self, node: usize) {
+        let parent = self.nodes[node].parent;
+        if self.nodes[parent].child == node {
+            self.nodes[parent].child = match self.nodes[node].right {
+                right if right == node => NIL,
+                right => right,
+            };
+        }
+        self.nodes[parent].degree -= 1;
+        self.remove_sibling(node);
+        self.nodes[node].parent = NIL;
+        self.nodes[node].marked = false;
+        self.splice(self.min, node);
+    }
+
+    /// Adds the single node to the roots.
+    fn add_root(&mut self, node: usize) {
+        if self.min == NIL {
+            self.min = node;
+            return;
+        }
+        self.splice(self.min, node);
+        if self.value(node) < self.value(self.min) {
+            self.min = node;
+        }
+    }
+
+    fn value(&self, node: usize) -> &T {
+        self.nodes[node]
+            .value
+            .as_ref()
+            .expect("link to popped node")
+    }
+}
+
+impl<T> FibonacciHeap<T> {
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    fn handle(&self, node: usize) -> Handle {
+        Handle {
+            index: node,
+            generation: self.nodes[node].generation,
+        }
+    }
+
+    /// Returns the node for the value, reusing the popped one if any.
+    fn alloc(&mut self, value: T) -> usize {
+        let index = match self.free.last() {
+            Some(&index) => index,
+            None => self.nodes.len(),
+        };
+        let node = Node {
+            value: Some(value),
+            generation: next_generation(),
+            parent: NIL,
+            child: NIL,
+            left: index,
+            right: index,
+            degree: 0,
+            marked: false,
+        };
+        match self.free.pop() {
+            Some(index) => self.nodes[index] = node,
+            None => self.nodes.push(node),
+        }
+        index
+    }
+
+    /// Inserts the single `node` after the `at` in its circular list.
+    fn splice(&mut self, at: usize, node: usize) {
+        let right = self.nodes[at].right;
+        self.nodes[node].left = at;
+        self.nodes[node].right = right;
+        self.nodes[at].right = node;
+        self.nodes[right].left = node;
+    }
+
+    /// Removes the node from its circular list, leaving it alone.
+    fn remove_sibling(&mut self, node: usize) {
+        let Node { left, right, .. } = self.nodes[node];
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+        self.nodes[node].left = node;
+        self.nodes[node].right = node;
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,155 @@
+use super::FibonacciHeap;
+
+use std::collections::{BTreeSet, HashMap};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::{MergeableHeap, MinHeap, PriorityQueue};
+
+#[test]
+fn test_insert_pop() {
+    let mut heap = FibonacciHeap::new();
+    assert!(heap.is_empty());
+    for value in [15, 11, 20, 10, 11] {
+        heap.insert(value);
+    }
+    assert_eq!(heap.len(), 5);
+    assert_eq!(heap.peek(), Some(&10));
+    let values: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+    assert_eq!(values, [10, 11, 11, 15, 20]);
+    assert_eq!(heap.peek(), None);
+}
+
+#[test]
+fn test_decrease_key() {
+    let mut heap = FibonacciHeap::new();
+    let handles: Vec<_> = (0..10).map(|value| heap.insert(value * 10)).collect();
+    heap.decrease_key(handles[7], 5);
+    heap.decrease_key(handles[0], -1);
+    heap.decrease_key(handles[3], 30);
+    assert_eq!(heap.get(handles[7]), Some(&5));
+    assert_eq!(heap.pop(), Some(-1));
+    assert_eq!(heap.get(handles[0]), None);
+    assert_eq!(heap.pop(), Some(5));
+    heap.decrease_key(handles[9], 15);
+    let values: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+    assert_eq!(values, [10, 15, 20, 30, 40, 50, 60, 80]);
+}
+
+#[test]
+#[should_panic(expected = "greater value")]
+fn test_increase_key() {
+    let mut heap = FibonacciHeap::new();
+    let handle = heap.insert(1);
+    heap.decrease_key(handle, 2);
+}
+
+#[test]
+fn test_stale_handle() {
+    let mut heap = FibonacciHeap::new();
+    let popped = heap.insert(1);
+    assert_eq!(heap.pop(), Some(1));
+    // the node of the popped value is reused.
+    let handle = heap.insert(2);
+    assert_eq!(heap.get(popped), None);
+    assert_eq!(heap.get(handle), Some(&2));
+    // nor is the handle of the other heap taken.
+    let other = FibonacciHeap::new().insert(0);
+    assert_eq!(heap.get(other), None);
+}
+
+#[test]
+#[should_panic(expected = "popped handle")]
+fn test_decrease_stale_key() {
+    let mut heap = FibonacciHeap::new();
+    let popped = heap.insert(1);
+    heap.pop();
+    heap.insert(2);
+    heap.decrease_key(popped, 0);
+}
+
+#[test]
+fn test_meld() {
+    let mut a = FibonacciHeap::new();
+    let mut b = FibonacciHeap::new();
+    let handles: Vec<_> = [5, 1, 9].into_iter().map(|v| a.insert(v)).collect();
+    for value in [4, 8, 2, 7] {
+        b.insert(value);
+    }
+    b.pop();
+    a.meld(b);
+    assert_eq!(a.len(), 6);
+    a.decrease_key(handles[2], 0);
+    a.meld(FibonacciHeap::new());
+    let mut empty = FibonacciHeap::new();
+    empty.meld(a);
+    let values: Vec<_> = std::iter::from_fn(|| empty.pop()).collect();
+    assert_eq!(values, [0, 1, 4, 5, 7, 8]);
+}
+
+#[test]
+fn test_priority_queue() {
+    fn drain<Q: PriorityQueue<u8>>(mut queue: Q) -> Vec<u8> {
+        queue.push(3);
+        queue.push(1);
+        queue.push(2);
+        assert_eq!(queue.len(), 3);
+        std::iter::from_fn(|| queue.pop()).collect()
+    }
+    assert_eq!(drain(FibonacciHeap::new()), drain(MinHeap::new(2)));
+}
+
+#[test]
+fn test_mergeable_heap() {
+    fn meld<H: MergeableHeap<u8> + Default>() -> Vec<u8> {
+        let (mut a, mut b) = (H::default(), H::default());
+        let handle = a.insert(3);
+        a.insert(2);
+        b.insert(1);
+        a.decrease_key(handle, 0);
+        a.meld(b);
+        std::iter::from_fn(|| a.pop()).collect()
+    }
+    assert_eq!(meld::<FibonacciHeap<_>>(), [0, 1, 2]);
+}
+
+#[test]
+fn test_against_btree_set() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut heap = FibonacciHeap::new();
+    // the (priority, id) pairs, of which ids make the values unique.
+    let mut oracle = BTreeSet::new();
+    let mut handles = HashMap::new();
+
+    for id in 0..10_000 {
+        match rng.gen_range(0..4) {
+            0 => {
+                let value = oracle.iter().next().copied();
+                if let Some((_, id)) = value {
+                    oracle.remove(&value.unwrap());
+                    handles.remove(&id);
+                }
+                assert_eq!(heap.pop(), value);
+            }
+            1 if id > 0 => {
+                let id = rng.gen_range(0..id);
+                if let Some(&handle) = handles.get(&id) {
+                    let (priority, _) = *heap.get(handle).unwrap();
+                    let value = (rng.gen_range(0..priority + 1), id);
+                    oracle.remove(&(priority, id));
+                    oracle.insert(value);
+                    heap.decrease_key(handle, value);
+                }
+            }
+            _ => {
+                let value = (rng.gen_range(0..1_000_000), id);
+                oracle.insert(value);
+                handles.insert(id, heap.insert(value));
+            }
+        }
+        assert_eq!(heap.len(), oracle.len());
+        assert_eq!(heap.peek(), oracle.iter().next());
+    }
+    assert!(std::iter::from_fn(|| heap.pop()).eq(oracle.into_iter()));
+}
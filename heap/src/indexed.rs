@@ -0,0 +1,202 @@
+//! An indexed N-arity max heap
+//!
+//! It's the same heap as [`Heap`] of which entries are the key/priority
+//! pairs, and the [`HMap`] keeps the position of each key in the heap,
+//! e.g. the priority of the key is changed or the key is removed in
+//! O(log n) without searching for it, which is what Dijkstra's and Prim's
+//! algorithms need.
+//!
+//! [`Heap`]: crate::Heap
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::Hash;
+use std::mem;
+use std::ops::Range;
+
+use hash_map::HMap;
+
+use crate::{children_indices, parent_index};
+
+/// A N-arity max heap of the keys by their priorities.
+pub struct IndexedHeap<K, P> {
+    /// The entries in the heap order.
+    data: Vec<(K, P)>,
+    /// The position of each key in `data`.
+    positions: HMap<K, usize>,
+    arity: usize,
+}
+
+impl<K: fmt::Debug, P: fmt::Debug> fmt::Debug for IndexedHeap<K, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedHeap")
+            .field("data", &self.data)
+            .field("arity", &self.arity)
+            .finish()
+    }
+}
+
+impl<K: Eq + Hash + Clone, P: Ord> IndexedHeap<K, P> {
+    /// Creates a max heap of which node has up to `arity` children, where
+    /// the priorities wrapped in [`Reverse`] make it a min heap.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `arity` is zero.
+    ///
+    /// [`Reverse`]: std::cmp::Reverse
+    pub fn new(arity: usize) -> Self {
+        assert!(arity > 0, "zero arity");
+        Self {
+            data: Vec::new(),
+            positions: HMap::new(),
+            arity,
+        }
+    }
+
+    /// Inserts the key with the priority, and returns the old priority
+    /// in case the key was already in the heap.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(&index) = self.positions.get(&key) {
+            return Some(self.replace(index, priority));
+        }
+        let index = self.data.len();
+        self.positions.insert(key.clone(), index);
+        self.data.push((key, priority));
+        self.bubble_up(index);
+        None
+    }
+
+    /// Pops the key of the highest priority.
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(self.remove_at(0))
+    }
+
+    /// Returns the key of the highest priority.
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.data.first().map(|(key, priority)| (key, priority))
+    }
+
+    /// Returns the priority of the key.
+    pub fn priority<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let index = self.positions.get(key)?;
+        Some(&self.data[*index].1)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.positions.get(key).is_some()
+    }
+
+    /// Changes the priority of the key in O(log n), and returns the old
+    /// priority in case the key was in the heap.
+    pub fn change_priority<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let index = *self.positions.get(key)?;
+        Some(self.replace(index, priority))
+    }
+
+    /// Removes the key in O(log n), and returns the priority in case
+    /// the key was in the heap.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let index = *self.positions.get(key)?;
+        Some(self.remove_at(index).1)
+    }
+
+    /// Replaces the priority of the entry and moves it to the place.
+    fn replace(&mut self, index: usize, priority: P) -> P {
+        let old = mem::replace(&mut self.data[index].1, priority);
+        if self.data[index].1 > old {
+            self.bubble_up(index);
+        } else {
+            self.push_down(index);
+        }
+        old
+    }
+
+    /// Removes the entry by moving the last one to its place.
+    fn remove_at(&mut self, index: usize) -> (K, P) {
+        let tail = self.data.len() - 1;
+        self.swap(index, tail);
+        let (key, priority) = self.data.pop().unwrap();
+        self.positions.remove(&key);
+        if index < self.data.len() {
+            self.bubble_up(index);
+            self.push_down(index);
+        }
+        (key, priority)
+    }
+
+    fn bubble_up(&mut self, mut index: usize) {
+        while let Some(parent) = parent_index(self.arity, index) {
+            if self.data[parent].1 >= self.data[index].1 {
+                break;
+            }
+            self.swap(parent, index);
+            index = parent;
+        }
+    }
+
+    fn push_down(&mut self, mut index: usize) {
+        while let Some(children) = children_indices(self.arity, self.data.len(), index) {
+            let max = self.max_index(children);
+            if self.data[index].1 >= self.data[max].1 {
+                break;
+            }
+            self.swap(index, max);
+            index = max;
+        }
+    }
+
+    /// Swaps the entries and their positions.
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+        for index in [a, b] {
+            *self.positions.get_mut(&self.data[index].0).unwrap() = index;
+        }
+    }
+
+    fn max_index(&self, children: Range<usize>) -> usize {
+        let start = children.start;
+        self.data[children]
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.1.cmp(&b.1))
+            .map(|(index, _)| start + index)
+            .unwrap()
+    }
+}
+
+impl<K, P> IndexedHeap<K, P> {
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub const fn arity(&self) -> usize {
+        self.arity
+    }
+}
+
+#[cfg(test)]
+mod test;
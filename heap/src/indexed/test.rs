@@ -0,0 +1,147 @@
+use super::IndexedHeap;
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn test_push_pop() {
+    let mut heap = IndexedHeap::new(2);
+    assert!(heap.is_empty());
+    assert_eq!(heap.push("a", 15), None);
+    assert_eq!(heap.push("b", 11), None);
+    assert_eq!(heap.push("c", 20), None);
+    assert_eq!(heap.push("a", 10), Some(15));
+    assert_eq!(heap.len(), 3);
+    assert_eq!(heap.peek(), Some((&"c", &20)));
+    assert_eq!(heap.pop(), Some(("c", 20)));
+    assert_eq!(heap.pop(), Some(("b", 11)));
+    assert_eq!(heap.pop(), Some(("a", 10)));
+    assert_eq!(heap.pop(), None);
+}
+
+#[test]
+fn test_change_priority() {
+    let mut heap = IndexedHeap::new(3);
+    for (key, priority) in [(1, 10), (2, 11), (3, 51), (4, 20), (5, 39)] {
+        heap.push(key, priority);
+    }
+    assert_eq!(heap.change_priority(&1, 55), Some(10));
+    assert_eq!(heap.change_priority(&3, 0), Some(51));
+    assert_eq!(heap.change_priority(&6, 0), None);
+    assert_eq!(heap.priority(&1), Some(&55));
+    assert!(heap.contains(&3));
+    assert!(!heap.contains(&6));
+    let keys: Vec<_> = std::iter::from_fn(|| heap.pop())
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(keys, [1, 5, 4, 2, 3]);
+}
+
+#[test]
+fn test_remove() {
+    let mut heap = IndexedHeap::new(2);
+    for key in 0..10 {
+        heap.push(key, key * 7 % 10);
+    }
+    assert_eq!(heap.remove(&3), Some(1));
+    assert_eq!(heap.remove(&3), None);
+    assert_eq!(heap.remove(&7), Some(9));
+    assert!(!heap.contains(&7));
+    assert_eq!(heap.len(), 8);
+    assert_eq!(heap.pop(), Some((4, 8)));
+}
+
+#[test]
+#[should_panic(expected = "zero arity")]
+fn test_zero_arity() {
+    IndexedHeap::<u8, u8>::new(0);
+}
+
+#[test]
+fn test_dijkstra() {
+    // the edges of (from, to, weight).
+    let edges = [
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 3, 11),
+        (2, 5, 2),
+        (3, 4, 6),
+        (4, 5, 9),
+    ];
+    let mut dists = [u32::MAX; 6];
+    let mut heap = IndexedHeap::new(4);
+    heap.push(0, Reverse(0));
+    while let Some((node, Reverse(dist))) = heap.pop() {
+        dists[node] = dist;
+        for &(a, b, weight) in edges.iter() {
+            let next = match node {
+                _ if node == a => b,
+                _ if node == b => a,
+                _ => continue,
+            };
+            if dists[next] != u32::MAX {
+                continue;
+            }
+            let dist = dist + weight;
+            match heap.priority(&next) {
+                Some(&Reverse(old)) if old <= dist => {}
+                _ => {
+                    heap.push(next, Reverse(dist));
+                }
+            }
+        }
+    }
+    assert_eq!(dists, [0, 7, 9, 20, 20, 11]);
+}
+
+#[test]
+fn test_against_btree_set() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for arity in 1..5 {
+        let mut heap = IndexedHeap::new(arity);
+        // the (priority, key) pairs, of which priorities are unique.
+        let mut oracle = BTreeSet::new();
+        let mut priorities = BTreeMap::new();
+
+        for _ in 0..5_000 {
+            let key = rng.gen_range(0..200);
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let priority = rng.gen::<u32>();
+                    let old = priorities.insert(key, priority);
+                    if let Some(old) = old {
+                        oracle.remove(&(old, key));
+                    }
+                    oracle.insert((priority, key));
+                    assert_eq!(heap.push(key, priority), old);
+                }
+                2 => {
+                    let old = priorities.remove(&key);
+                    if let Some(old) = old {
+                        oracle.remove(&(old, key));
+                    }
+                    assert_eq!(heap.remove(&key), old);
+                }
+                _ => {
+                    let want = oracle.iter().next_back().copied();
+                    if let Some((priority, key)) = want {
+                        oracle.remove(&(priority, key));
+                        priorities.remove(&key);
+                    }
+                    assert_eq!(heap.pop().map(|(k, p)| (p, k)), want);
+                }
+            }
+            assert_eq!(heap.len(), oracle.len());
+            assert_eq!(
+                heap.peek().map(|(k, p)| (*p, *k)),
+                oracle.iter().next_back().copied()
+            );
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! A N-arity heap.
+//!
+//! The pairing and the Fibonacci heaps keep their nodes in the arena, e.g.
+//! their meld takes O(m) to move the nodes of the other heap of m values
+//! into the arena, rather than O(1) of the heaps on the pointers.
+
+#![forbid(missing_debug_implementations)]
+
+use std::cmp::Ordering;
+use std::ops::Range;
+use std::slice;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+pub use fibonacci::FibonacciHeap;
+pub use indexed::IndexedHeap;
+pub use pairing::PairingHeap;
+
+mod fibonacci;
+mod indexed;
+mod pairing;
+
+// The generation of the next node, unique across all the heaps not to
+// mistake a node reusing the index for the popped one.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// A min priority queue of which `pop` returns the least element first.
+///
+/// It's implemented by [`MinHeap`], [`PairingHeap`] and [`FibonacciHeap`],
+/// but not by [`Heap`] of which order is up to its comparator.
+pub trait PriorityQueue<T> {
+    fn push(&mut self, value: T);
+    fn pop(&mut self) -> Option<T>;
+    fn peek(&self) -> Option<&T>;
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A [`PriorityQueue`] of which values are kept by the handles to be
+/// decreased, and of which heaps are melded into one.
+///
+/// It's implemented by [`PairingHeap`] and [`FibonacciHeap`].
+pub trait MergeableHeap<T>: PriorityQueue<T> + Sized {
+    /// Inserts the value, and returns the handle of it.
+    fn insert(&mut self, value: T) -> Handle;
+
+    /// Returns the value of the handle, or `None` once it's popped.
+    fn get(&self, handle: Handle) -> Option<&T>;
+
+    /// Decreases the value of the handle.
+    ///
+    /// # Panics
+    ///
+    /// It panics when the handle is popped or the value is greater than
+    /// the current one.
+    fn decrease_key(&mut self, handle: Handle, value: T);
+
+    /// Moves all the values of the `other` into the heap.
+    fn meld(&mut self, other: Self);
+}
+
+/// A handle of the element in [`PairingHeap`] or [`FibonacciHeap`].
+///
+/// It stays valid until the element is popped, after which it refers to
+/// nothing even if the element inserted later reuses the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: u64,
+}
+
+/// Returns the generation of the new node.
+fn next_generation() -> u64 {
+    GENERATION.fetch_add(1, Relaxed)
+}
+
+/// A N-arity heap of which top is the greatest element by the comparator.
+///
+/// It's a max heap by [`new`], and a min heap by [`new_min`] or with
+/// the elements wrapped in [`Reverse`].  [`with_cmp`] takes any total
+/// order, e.g. [`f64::total_cmp`] for the floats.
+///
+/// [`new`]: Heap::new
+/// [`new_min`]: Heap::new_min
+/// [`with_cmp`]: Heap::with_cmp
+/// [`Reverse`]: std::cmp::Reverse
+#[derive(Clone, Debug)]
+pub struct Heap<T> {
+    data: Vec<T>,
+    arity: usize,
+    cmp: fn(&T, &T) -> Ordering,
+}
+
+impl<T: Ord> Default for Heap<T> {
+    /// Creates an empty binary max heap.
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for Heap<T> {
+    /// Creates a binary max heap of the elements in O(n).
+    fn from(data: Vec<T>) -> Self {
+        let mut heap = Self::new(2);
+        heap.data = data;
+        heap.heapify();
+        heap
+    }
+}
+
+impl<T: Ord> FromIterator<T> for Heap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T> Extend<T> for Heap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: Ord> Heap<T> {
+    /// Creates a max heap of which node has up to `arity` children.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `arity` is zero.
+    pub fn new(arity: usize) -> Self {
+        Self::with_cmp(arity, T::cmp)
+    }
+
+    /// Creates a min heap of which node has up to `arity` children.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `arity` is zero.
+    pub fn new_min(arity: usize) -> Self {
+        Self::with_cmp(arity, |a, b| b.cmp(a))
+    }
+}
+
+impl<T> Heap<T> {
+    /// Creates a heap of which top is the greatest element by the `cmp`.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `arity` is zero.
+    pub fn with_cmp(arity: usize, cmp: fn(&T, &T) -> Ordering) -> Self {
+        assert!(arity > 0, "zero arity");
+        Self {
+            data: Vec::new(),
+            arity,
+            cmp,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub const fn arity(&self) -> usize {
+        self.arity
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Pushes the value to the heap in O(log n).
+    pub fn push(&mut self, value: T) {
+        let index = self.data.len();
+        self.data.push(value);
+        self.bubble_up(index);
+    }
+
+    /// Pops the greatest value from the heap in O(log n).
+    pub fn pop(&mut self) -> Option<T> {
+        let tail = self.data.len().checked_sub(1)?;
+        self.data.swap(0, tail);
+        let value = self.data.pop();
+        self.push_down(0, tail);
+        value
+    }
+
+    /// Returns the greatest value.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Inserts the new value to the heap, same as [`push`].
+    ///
+    /// [`push`]: Heap::push
+    pub fn insert(&mut self, value: T) {
+        self.push(value);
+    }
+
+    /// Pop the highest priority item from the heap, same as [`pop`].
+    ///
+    /// [`pop`]: Heap::pop
+    pub fn top(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    /// Update the first find `value` to the `new` value.
+    ///
+    /// It returns `false` in case there is no matched value.  It finds
+    /// the value by the linear scan, e.g. [`IndexedHeap`] is the one for
+    /// the frequent updates.
+    pub fn update(&mut self, old: T, new: T) -> bool
+    where
+        T: PartialEq,
+    {
+        let ordering = match (self.cmp)(&new, &old) {
+            Ordering::Equal => return false,
+            ordering => ordering,
+        };
+        let index = match self.data.iter().position(|v| *v == old) {
+            None => return false,
+            Some(index) => index,
+        };
+        self.data[index] = new;
+        match ordering {
+            Ordering::Greater => self.bubble_up(index),
+            _ => self.push_down(index, self.data.len()),
+        }
+        true
+    }
+
+    /// Returns the iterator over the values in arbitrary order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Returns the values in arbitrary order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the values in the ascending order by the comparator,
+    /// sorting them in place in O(n log n).
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            self.push_down(0, end);
+        }
+        self.data
+    }
+
+    /// Makes the values a heap in O(n) by pushing down the parents from
+    /// the last one.
+    fn heapify(&mut self) {
+        let len = self.data.len();
+        if let Some(last) = len
+            .checked_sub(1)
+            .and_then(|last| parent_index(self.arity, last))
+        {
+            for index in (0..=last).rev() {
+                self.push_down(index, len);
+            }
+        }
+    }
+
+    fn bubble_up(&mut self, mut index: usize) {
+        debug_assert!(index < self.data.len());
+        while let Some(parent) = parent_index(self.arity, index) {
+            if !self.greater(index, parent) {
+                break;
+            }
+            self.data.swap(parent, index);
+            index = parent;
+        }
+    }
+
+    /// Pushes down the value within the first `len` values.
+    fn push_down(&mut self, mut index: usize, len: usize) {
+        while let Some(children) = children_indices(self.arity, len, index) {
+            let max = self.max_index(children);
+            if !self.greater(max, index) {
+                break;
+            }
+            self.data.swap(index, max);
+            index = max;
+        }
+    }
+
+    fn greater(&self, a: usize, b: usize) -> bool {
+        (self.cmp)(&self.data[a], &self.data[b]) == Ordering::Greater
+    }
+
+    fn max_index(&self, children: Range<usize>) -> usize {
+        let start = children.start;
+        self.data[children]
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| (self.cmp)(a, b))
+            .map(|(index, _)| start + index)
+            .unwrap()
+    }
+}
+
+/// A N-arity min heap, e.g. [`Heap`] by [`Heap::new_min`] of which order
+/// can't be changed, to be a [`PriorityQueue`].
+#[derive(Clone, Debug)]
+pub struct MinHeap<T>(Heap<T>);
+
+impl<T: Ord> Default for MinHeap<T> {
+    /// Creates an empty binary min heap.
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl<T: Ord> MinHeap<T> {
+    /// Creates a min heap of which node has up to `arity` children.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `arity` is zero.
+    pub fn new(arity: usize) -> Self {
+        Self(Heap::new_min(arity))
+    }
+}
+
+impl<T> MinHeap<T> {
+    /// Returns the heap, of which top is the least element.
+    pub fn into_heap(self) -> Heap<T> {
+        self.0
+    }
+}
+
+impl<T> PriorityQueue<T> for MinHeap<T> {
+    fn push(&mut self, value: T) {
+        self.0.push(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.0.peek()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Heap<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+fn parent_index(arity: usize, child: usize) -> Option<usize> {
+    match child {
+        0 => None,
+        _ => Some((child - 1) / arity),
+    }
+}
+
+fn children_indices(arity: usize, len: usize, parent: usize) -> Option<Range<usize>> {
+    let start = parent * arity + 1;
+    if start >= len {
+        return None;
+    }
+    let end = if start + arity < len {
+        start + arity
+    } else {
+        len
+    };
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod test;
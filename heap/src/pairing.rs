@@ -0,0 +1,269 @@
+//! A pairing heap
+//!
+//! The heap is a tree of which children are linked as the list of
+//! siblings, and the nodes live in the arena and link each other by
+//! the index.  The insert and the decrease-key link a node to the root
+//! in O(1), and the pop pairs up the children of the root left to right
+//! and links the pairs right to left in amortized O(log n).  The meld
+//! links the roots in O(1), but only after it moves the nodes of the other
+//! heap into the arena in O(m).
+
+use std::mem;
+
+use crate::{next_generation, Handle, MergeableHeap, PriorityQueue};
+
+// The null link.
+const NIL: usize = usize::MAX;
+
+/// A min heap based on the pairing heap.
+#[derive(Debug)]
+pub struct PairingHeap<T> {
+    nodes: Vec<Node<T>>,
+    /// The popped nodes to be reused.
+    free: Vec<usize>,
+    root: usize,
+    len: usize,
+}
+
+#[derive(Debug)]
+struct Node<T> {
+    /// The value, or `None` once popped.
+    value: Option<T>,
+    /// Tells the node from the popped one of the same index.
+    generation: u64,
+    /// The first child.
+    child: usize,
+    /// The next sibling.
+    next: usize,
+    /// The parent for the first child, or the previous sibling.
+    prev: usize,
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for PairingHeap<T> {
+    fn push(&mut self, value: T) {
+        self.insert(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        PairingHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        PairingHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for PairingHeap<T> {
+    fn insert(&mut self, value: T) -> Handle {
+        PairingHeap::insert(self, value)
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        PairingHeap::get(self, handle)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, value: T) {
+        PairingHeap::decrease_key(self, handle, value);
+    }
+
+    fn meld(&mut self, other: Self) {
+        PairingHeap::meld(self, other);
+    }
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            len: 0,
+        }
+    }
+
+    /// Inserts the value in O(1), and returns the handle of it.
+    pub fn insert(&mut self, value: T) -> Handle {
+        let node = self.alloc(value);
+        self.root = self.link(self.root, node);
+        self.len += 1;
+        self.handle(node)
+    }
+
+    /// Pops the least value in amortized O(log n).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.root == NIL {
+            return None;
+        }
+        let root = mem::replace(&mut self.root, NIL);
+        let mut pairs = Vec::new();
+        let mut next = mem::replace(&mut self.nodes[root].child, NIL);
+        while next != NIL {
+            let a = next;
+            let b = self.detach(a);
+            next = if b == NIL { NIL } else { self.detach(b) };
+            pairs.push(self.link(a, b));
+        }
+        for pair in pairs.into_iter().rev() {
+            self.root = self.link(pair, self.root);
+        }
+        self.free.push(root);
+        self.len -= 1;
+        self.nodes[root].value.take()
+    }
+
+    /// Returns the least value.
+    pub fn peek(&self) -> Option<&T> {
+        match self.root {
+            NIL => None,
+            root => self.nodes[root].value.as_ref(),
+        }
+    }
+
+    /// Returns the value of the handle, or `None` once it's popped.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes
+            .get(handle.index)
+            .filter(|node| node.generation == handle.generation)?
+            .value
+            .as_ref()
+    }
+
+    /// Decreases the value of the handle in O(1) by cutting it off from
+    /// its parent and linking it to the root.
+    ///
+    /// # Panics
+    ///
+    /// It panics when the handle is popped or the value is greater than
+    /// the current one.
+    pub fn decrease_key(&mut self, handle: Handle, value: T) {
+        let node = handle.index;
+        let current = self.get(handle).expect("popped handle");
+        assert!(value <= *current, "greater value");
+        self.nodes[node].value = Some(value);
+        if node == self.root {
+            return;
+        }
+        let (prev, next) = (self.nodes[node].prev, self.detach(node));
+        if self.nodes[prev].child == node {
+            self.nodes[prev].child = next;
+        } else {
+            self.nodes[prev].next = next;
+        }
+        if next != NIL {
+            self.nodes[next].prev = prev;
+        }
+        self.root = self.link(self.root, node);
+    }
+
+    /// Moves all the values of the `other` into the heap.
+    ///
+    /// It links the roots in O(1) after moving the nodes of the `other`
+    /// into the arena of the heap in O(m) without any comparison, and
+    /// the handles of the `other` are no longer valid unless the heap is
+    /// empty.
+    pub fn meld(&mut self, mut other: Self) {
+        if self.len == 0 {
+            mem::swap(self, &mut other);
+            return;
+        }
+        let offset = self.nodes.len();
+        let shift = |link: usize| if link == NIL { NIL } else { link + offset };
+        self.nodes.extend(other.nodes.drain(..).map(|node| Node {
+            child: shift(node.child),
+            next: shift(node.next),
+            prev: shift(node.prev),
+            ..node
+        }));
+        self.free.extend(other.free.drain(..).map(shift));
+        self.root = self.link(self.root, shift(other.root));
+        self.len += other.len;
+    }
+
+    /// Links the two trees by making the greater root the first child of
+    /// the other, and returns the new root.
+    fn link(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        let (parent, child) = if self.value(b) < self.value(a) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let first = self.nodes[parent].child;
+        if first != NIL {
+            self.nodes[first].prev = child;
+        }
+        self.nodes[child].next = first;
+        self.nodes[child].prev = parent;
+        self.nodes[parent].child = child;
+        parent
+    }
+
+    fn value(&self, node: usize) -> &T {
+        self.nodes[node]
+            .value
+            .as_ref()
+            .expect("link to popped node")
+    }
+}
+
+impl<T> PairingHeap<T> {
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    fn handle(&self, node: usize) -> Handle {
+        Handle {
+            index: node,
+            generation: self.nodes[node].generation,
+        }
+    }
+
+    /// Returns the node for the value, reusing the popped one if any.
+    fn alloc(&mut self, value: T) -> usize {
+        let node = Node {
+            value: Some(value),
+            generation: next_generation(),
+            child: NIL,
+            next: NIL,
+            prev: NIL,
+        };
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Clears the sibling links of the node, and returns the next sibling.
+    fn detach(&mut self, node: usize) -> usize {
+        self.nodes[node].prev = NIL;
+        mem::replace(&mut self.nodes[node].next, NIL)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -1,5 +1,11 @@
 use super::Heap;
 
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
 #[test]
 fn binary_heap() {
     let mut heap = Heap::new(2);
@@ -23,3 +29,84 @@ fn ternary_heap() {
     assert_eq!(heap.top(), Some(11));
     assert_eq!(heap.top(), Some(10));
 }
+
+#[test]
+fn min_heap() {
+    let mut heap = Heap::new_min(4);
+    heap.extend([15, 11, 10, 20]);
+    assert_eq!(heap.len(), 4);
+    assert_eq!(heap.peek(), Some(&10));
+    assert_eq!(heap.pop(), Some(10));
+    assert_eq!(heap.pop(), Some(11));
+    assert_eq!(heap.into_sorted_vec(), [20, 15]);
+}
+
+#[test]
+fn reverse_heap() {
+    let mut heap: Heap<_> = [15, 11, 10].into_iter().map(Reverse).collect();
+    assert_eq!(heap.pop(), Some(Reverse(10)));
+    assert_eq!(heap.pop(), Some(Reverse(11)));
+}
+
+#[test]
+fn float_heap() {
+    let mut heap = Heap::with_cmp(2, f64::total_cmp);
+    heap.extend([1.5, f64::NAN, -0.5, 2.0]);
+    assert!(heap.pop().unwrap().is_nan());
+    assert_eq!(heap.pop(), Some(2.0));
+    assert_eq!(heap.pop(), Some(1.5));
+    assert_eq!(heap.pop(), Some(-0.5));
+    assert_eq!(heap.pop(), None);
+}
+
+#[test]
+fn update() {
+    let mut heap = Heap::new(3);
+    heap.extend([10, 11, 51, 20, 39, 15]);
+    assert!(heap.update(10, 55));
+    assert!(heap.update(51, 1));
+    assert!(!heap.update(10, 5));
+    assert_eq!(heap.into_sorted_vec(), [1, 11, 15, 20, 39, 55]);
+}
+
+#[test]
+#[should_panic(expected = "zero arity")]
+fn zero_arity() {
+    Heap::<u8>::new(0);
+}
+
+#[test]
+fn from_vec() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for n in [0, 1, 2, 3, 10, 1_000] {
+        let data: Vec<u16> = (0..n).map(|_| rng.gen()).collect();
+        let heap = Heap::from(data.clone());
+        assert_eq!(heap.len(), n);
+        assert_eq!(heap.peek(), data.iter().max());
+        let mut sorted = data;
+        sorted.sort();
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+}
+
+#[test]
+fn against_binary_heap() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for arity in 1..6 {
+        let mut heap = Heap::new(arity);
+        let mut oracle = BinaryHeap::new();
+
+        for _ in 0..5_000 {
+            if rng.gen_range(0..3) == 0 {
+                assert_eq!(heap.pop(), oracle.pop());
+            } else {
+                let value = rng.gen_range(0..1_000);
+                heap.push(value);
+                oracle.push(value);
+            }
+            assert_eq!(heap.len(), oracle.len());
+            assert_eq!(heap.peek(), oracle.peek());
+        }
+        assert_eq!(heap.into_sorted_vec(), oracle.into_sorted_vec());
+    }
+}
@@ -4,6 +4,8 @@
 
 use std::collections::BTreeMap;
 
+use render::Render;
+
 #[derive(Debug)]
 pub struct HScore {
     h: HuffmanNode,
@@ -12,11 +14,46 @@ pub struct HScore {
 
 #[derive(Debug)]
 pub enum HuffmanNode {
-    Tree(Box<Self>, Box<Self>),
-    Leaf(char),
+    /// The subtrees and the total weight.
+    Tree(Box<Self>, Box<Self>, i32),
+    /// The character and its weight, e.g. the number of occurrences.
+    Leaf(char, i32),
+}
+
+impl Render for HuffmanNode {
+    fn label(&self) -> String {
+        match self {
+            Self::Tree(..) => "*".to_string(),
+            Self::Leaf(c, _) => format!("{c:?}"),
+        }
+    }
+
+    fn info(&self) -> Option<String> {
+        Some(format!("w={}", self.weight()))
+    }
+
+    fn left(&self) -> Option<&Self> {
+        match self {
+            Self::Tree(l, _, _) => Some(l),
+            Self::Leaf(..) => None,
+        }
+    }
+
+    fn right(&self) -> Option<&Self> {
+        match self {
+            Self::Tree(_, r, _) => Some(r),
+            Self::Leaf(..) => None,
+        }
+    }
 }
 
 impl HuffmanNode {
+    pub fn weight(&self) -> i32 {
+        match self {
+            Self::Tree(_, _, weight) | Self::Leaf(_, weight) => *weight,
+        }
+    }
+
     pub fn encode_str(&self, s: &str) -> Option<Vec<char>> {
         let mut result = Vec::new();
         for c in s.chars() {
@@ -33,7 +70,7 @@ impl HuffmanNode {
 
     pub fn encode_char(&self, c: char) -> Option<Vec<char>> {
         match self {
-            Self::Tree(l, r) => {
+            Self::Tree(l, r, _) => {
                 if let Some(mut v) = l.encode_char(c) {
                     v.insert(0, '0');
                     return Some(v);
@@ -44,7 +81,7 @@ impl HuffmanNode {
                 }
                 None
             }
-            Self::Leaf(leaf) => {
+            Self::Leaf(leaf, _) => {
                 if c == *leaf {
                     Some(Vec::new())
                 } else {
@@ -56,12 +93,12 @@ impl HuffmanNode {
 
     pub fn print_depth_first(&self, depth: usize, dir: char) {
         match self {
-            Self::Tree(l, r) => {
+            Self::Tree(l, r, _) => {
                 l.print_depth_first(depth + 1, '/');
                 println!("{:.<depth$}{}*", "", dir);
                 r.print_depth_first(depth + 1, '\\');
             }
-            Self::Leaf(c) => {
+            Self::Leaf(c, _) => {
                 println!("{:.<depth$}{}{}", "", dir, c);
             }
         }
@@ -77,7 +114,7 @@ pub fn build_tree(s: &str) -> HuffmanNode {
     let mut tlist: Vec<HScore> = map
         .into_iter()
         .map(|(k, v)| HScore {
-            h: HuffmanNode::Leaf(k),
+            h: HuffmanNode::Leaf(k, v),
             score: v,
         })
         .collect();
@@ -97,7 +134,7 @@ pub fn build_tree(s: &str) -> HuffmanNode {
         let a = tlist.pop().unwrap();
         let b = tlist.pop().unwrap();
         tlist.push(HScore {
-            h: HuffmanNode::Tree(Box::new(a.h), Box::new(b.h)),
+            h: HuffmanNode::Tree(Box::new(a.h), Box::new(b.h), a.score + b.score),
             score: a.score + b.score,
         });
     }
@@ -110,6 +147,8 @@ fn main() {
     println!("{s}");
     let tree = build_tree(s);
     tree.print_depth_first(0, '<');
+    print!("\n{}", render::boxes(Some(&tree)));
+    print!("\n{}", render::dot(Some(&tree)));
 
     println!("\nencoding");
     let mut chars = std::collections::BTreeSet::new();
@@ -0,0 +1,394 @@
+//! A singly linked list
+
+#![forbid(unsafe_code, missing_debug_implementations)]
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::mem;
+
+/// A singly linked list.
+///
+/// All the operations walk the list in the loop instead of the recursion,
+/// so that they don't overflow the stack on the long list.
+pub struct LinkedList<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    data: T,
+    next: Link<T>,
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        // unlinks the nodes one by one not to drop them recursively.
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    /// Appends the elements to the back in O(n + m).
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut len = self.len;
+        let mut link = self.last_link();
+        for data in iter {
+            let node = link.insert(Box::new(Node { data, next: None }));
+            link = &mut node.next;
+            len += 1;
+        }
+        self.len = len;
+    }
+}
+
+impl<T> LinkedList<T> {
+    pub const fn new() -> Self {
+        Self { head: None, len: 0 }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn push_front(&mut self, data: T) {
+        let next = self.head.take();
+        self.head = Some(Box::new(Node { data, next }));
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.next;
+        self.len -= 1;
+        Some(node.data)
+    }
+
+    /// Returns the front element.
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.data)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.data)
+    }
+
+    /// Appends the element to the back in O(n).
+    pub fn push_back(&mut self, data: T) {
+        *self.last_link() = Some(Box::new(Node { data, next: None }));
+        self.len += 1;
+    }
+
+    /// Inserts the element before the first greater or equal one, which
+    /// keeps the sorted list sorted.
+    pub fn insert(&mut self, data: T)
+    where
+        T: PartialOrd,
+    {
+        let mut link = &mut self.head;
+        while matches!(link, Some(node) if node.data < data) {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let next = link.take();
+        *link = Some(Box::new(Node { data, next }));
+        self.len += 1;
+    }
+
+    /// Removes the first element matching the predicate.
+    pub fn remove_first<F>(&mut self, mut pred: F) -> Option<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut link = &mut self.head;
+        while matches!(link, Some(node) if !pred(&node.data)) {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let node = link.take()?;
+        *link = node.next;
+        self.len -= 1;
+        Some(node.data)
+    }
+
+    /// Sorts the list in place in O(n log n), keeping the order of
+    /// the equal elements.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list with the comparator, relinking the nodes by
+    /// the bottom-up merge sort.
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // the sorted runs of 2^i nodes, of which higher ones hold
+        // the earlier nodes.
+        let mut runs: Vec<Link<T>> = Vec::new();
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+            let mut carry = Some(node);
+            for run in runs.iter_mut() {
+                match run.take() {
+                    Some(run) => carry = merge(Some(run), carry, &mut cmp),
+                    None => {
+                        *run = carry.take();
+                        break;
+                    }
+                }
+            }
+            if carry.is_some() {
+                runs.push(carry);
+            }
+        }
+        self.head = runs
+            .into_iter()
+            .fold(None, |sorted, run| merge(run, sorted, &mut cmp));
+    }
+
+    /// Removes the consecutive equal elements, e.g. all the duplicates
+    /// of the sorted list.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut removed = 0;
+        let mut next = self.head.as_deref_mut();
+        while let Some(node) = next {
+            while matches!(node.next, Some(ref dup) if dup.data == node.data) {
+                let dup = node.next.take().unwrap();
+                node.next = dup.next;
+                removed += 1;
+            }
+            next = node.next.as_deref_mut();
+        }
+        self.len -= removed;
+    }
+
+    /// Reverses the list in place.
+    pub fn reverse(&mut self) {
+        let mut prev = None;
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = mem::replace(&mut node.next, prev);
+            prev = Some(node);
+        }
+        self.head = prev;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+            len: self.len,
+        }
+    }
+
+    /// Returns the link after the last node.
+    fn last_link(&mut self) -> &mut Link<T> {
+        let mut link = &mut self.head;
+        while link.is_some() {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        link
+    }
+}
+
+/// Merges the two sorted lists into one sorted list by relinking
+/// the nodes, taking the element of `a` first for the equal ones.
+pub fn merge_sorted<T: Ord>(mut a: LinkedList<T>, mut b: LinkedList<T>) -> LinkedList<T> {
+    LinkedList {
+        len: a.len + b.len,
+        head: merge(a.head.take(), b.head.take(), &mut T::cmp),
+    }
+}
+
+/// Merges the two sorted chains of the nodes.
+fn merge<T, F>(mut a: Link<T>, mut b: Link<T>, cmp: &mut F) -> Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut head = None;
+    let mut tail = &mut head;
+    while let (Some(x), Some(y)) = (&a, &b) {
+        // takes the one of `a` unless `b` is less for the stability.
+        let from = if cmp(&y.data, &x.data) == Ordering::Less {
+            &mut b
+        } else {
+            &mut a
+        };
+        let mut node = from.take().unwrap();
+        *from = node.next.take();
+        tail = &mut tail.insert(node).next;
+    }
+    *tail = if a.is_some() { a } else { b };
+    head
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+/// An iterator over the elements of [`LinkedList`].
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        self.len -= 1;
+        Some(&node.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over the mutable elements of [`LinkedList`].
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    len: usize,
+}
+
+impl<T> Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterMut").field("len", &self.len).finish()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+        self.len -= 1;
+        Some(&mut node.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of [`LinkedList`].
+#[derive(Debug)]
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,192 @@
+use super::{merge_sorted, LinkedList};
+
+use std::collections::VecDeque;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn test_push_pop() {
+    let mut list = LinkedList::new();
+    assert!(list.is_empty());
+    list.push_front(2);
+    list.push_front(1);
+    list.push_back(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.peek(), Some(&1));
+    *list.peek_mut().unwrap() = 0;
+    assert_eq!(format!("{list:?}"), "[0, 2, 3]");
+    assert_eq!(list.pop_front(), Some(0));
+    assert_eq!(list.pop_front(), Some(2));
+    assert_eq!(list.pop_front(), Some(3));
+    assert_eq!(list.pop_front(), None);
+    assert_eq!(list.peek(), None);
+}
+
+#[test]
+fn test_no_bounds() {
+    // neither `Debug` nor `PartialOrd`.
+    struct Opaque;
+
+    let mut list = LinkedList::new();
+    list.push_front(Opaque);
+    list.push_back(Opaque);
+    assert_eq!(list.iter().count(), 2);
+}
+
+#[test]
+fn test_insert() {
+    let mut list = LinkedList::new();
+    for x in [5, 1, 4, 1, 3, 9, 2] {
+        list.insert(x);
+    }
+    assert!(list.iter().eq(&[1, 1, 2, 3, 4, 5, 9]));
+}
+
+#[test]
+fn test_remove_first() {
+    let mut list: LinkedList<_> = (0..6).collect();
+    assert_eq!(list.remove_first(|x| x % 2 == 1), Some(1));
+    assert_eq!(list.remove_first(|x| *x > 4), Some(5));
+    assert_eq!(list.remove_first(|x| *x > 4), None);
+    assert_eq!(list.remove_first(|x| *x == 0), Some(0));
+    assert!(list.iter().eq(&[2, 3, 4]));
+    assert_eq!(list.len(), 3);
+}
+
+#[test]
+fn test_reverse() {
+    let mut list: LinkedList<_> = (0..5).collect();
+    list.reverse();
+    assert!(list.iter().copied().eq((0..5).rev()));
+    list.push_back(-1);
+    assert_eq!(list.len(), 6);
+
+    let mut empty = LinkedList::<i32>::new();
+    empty.reverse();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_sort() {
+    let mut rng = StdRng::seed_from_u64(55);
+    for n in [0, 1, 2, 3, 7, 64, 1_000] {
+        let mut oracle: Vec<u8> = (0..n).map(|_| rng.gen()).collect();
+        let mut list: LinkedList<_> = oracle.iter().copied().collect();
+        list.sort();
+        oracle.sort();
+        assert_eq!(list.len(), n);
+        assert!(list.iter().eq(oracle.iter()));
+    }
+}
+
+#[test]
+fn test_sort_stable() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut oracle: Vec<(u8, usize)> = (0..1_000).map(|i| (rng.gen_range(0..10), i)).collect();
+    let mut list: LinkedList<_> = oracle.iter().copied().collect();
+    list.sort_by(|a, b| a.0.cmp(&b.0));
+    oracle.sort_by_key(|x| x.0);
+    assert!(list.iter().eq(oracle.iter()));
+}
+
+#[test]
+fn test_merge_sorted() {
+    let a: LinkedList<_> = [1, 3, 3, 5].into_iter().collect();
+    let b: LinkedList<_> = [0, 3, 4, 6, 7].into_iter().collect();
+    let list = merge_sorted(a, b);
+    assert_eq!(list.len(), 9);
+    assert_eq!(format!("{list:?}"), "[0, 1, 3, 3, 3, 4, 5, 6, 7]");
+    let list = merge_sorted(list, LinkedList::new());
+    assert_eq!(list.len(), 9);
+    let list = merge_sorted(LinkedList::new(), list);
+    assert_eq!(list.len(), 9);
+}
+
+#[test]
+fn test_dedup() {
+    let mut list: LinkedList<_> = [1, 1, 2, 3, 3, 3, 1, 4, 4].into_iter().collect();
+    list.dedup();
+    assert_eq!(list.len(), 5);
+    assert_eq!(format!("{list:?}"), "[1, 2, 3, 1, 4]");
+
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut oracle: Vec<u8> = (0..1_000).map(|_| rng.gen_range(0..50)).collect();
+    let mut list: LinkedList<_> = oracle.iter().copied().collect();
+    list.sort();
+    list.dedup();
+    oracle.sort();
+    oracle.dedup();
+    assert_eq!(list.len(), oracle.len());
+    assert!(list.iter().eq(oracle.iter()));
+}
+
+#[test]
+fn test_iter() {
+    let mut list: LinkedList<_> = (0..5).collect();
+    let mut iter = list.iter();
+    iter.next();
+    assert_eq!(iter.len(), 4);
+    for x in &mut list {
+        *x *= 10;
+    }
+    assert!(list.iter().eq(&[0, 10, 20, 30, 40]));
+    assert_eq!(
+        list.into_iter().collect::<Vec<_>>(),
+        vec![0, 10, 20, 30, 40]
+    );
+}
+
+#[test]
+fn test_long_list() {
+    const N: usize = 1_000_000;
+
+    let mut list: LinkedList<_> = (0..N).collect();
+    list.push_back(N);
+    list.insert(N + 1);
+    assert_eq!(list.remove_first(|x| *x == N + 1), Some(N + 1));
+    list.reverse();
+    assert_eq!(list.peek(), Some(&N));
+    assert_eq!(list.len(), N + 1);
+    list.sort();
+    list.dedup();
+    assert_eq!(list.peek(), Some(&0));
+    assert_eq!(list.len(), N + 1);
+    drop(list);
+}
+
+#[test]
+fn test_against_vec_deque() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut list = LinkedList::new();
+    let mut oracle = VecDeque::new();
+
+    for i in 0..5_000 {
+        match rng.gen_range(0..5) {
+            0 => {
+                list.push_front(i);
+                oracle.push_front(i);
+            }
+            1 => {
+                list.push_back(i);
+                oracle.push_back(i);
+            }
+            2 => assert_eq!(list.pop_front(), oracle.pop_front()),
+            3 => {
+                let x = rng.gen_range(0..i + 1);
+                let want = oracle
+                    .iter()
+                    .position(|y| *y >= x)
+                    .and_then(|pos| oracle.remove(pos));
+                assert_eq!(list.remove_first(|y| *y >= x), want);
+            }
+            _ => {
+                list.reverse();
+                oracle.make_contiguous().reverse();
+            }
+        }
+        assert_eq!(list.len(), oracle.len());
+        assert_eq!(list.peek(), oracle.front());
+    }
+    assert!(list.iter().eq(oracle.iter()));
+}
@@ -0,0 +1,262 @@
+//! An LRU cache
+
+#![forbid(unsafe_code, missing_debug_implementations)]
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::Hash;
+use std::mem;
+
+use dlist::arena::{Handle, LinkedList};
+use hash_map::HMap;
+
+/// A cache hit statistics.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    /// The number of entries evicted for the capacity.
+    pub evictions: usize,
+}
+
+impl Stats {
+    /// Returns the ratio of the hits to the lookups, or zero without
+    /// any lookups.
+    pub fn hit_ratio(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+/// A least recently used cache.
+///
+/// The entries are kept in the [`LinkedList`] from the least recently used
+/// to the most recently used, and the [`HMap`] finds the entry of the key
+/// in the list, e.g. all the operations take O(1).
+///
+/// The capacity is the total weight of the entries, which is one for each
+/// entry unless the cache is created by [`with_weigher`].
+///
+/// [`with_weigher`]: LruCache::with_weigher
+pub struct LruCache<K, V> {
+    map: HMap<K, Handle>,
+    list: LinkedList<Entry<K, V>>,
+    capacity: usize,
+    /// The total weight of the entries.
+    weight: usize,
+    weigher: fn(&K, &V) -> usize,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+    stats: Stats,
+}
+
+#[derive(Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    weight: usize,
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LruCache")
+            .field("list", &self.list)
+            .field("capacity", &self.capacity)
+            .field("weight", &self.weight)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+    /// Creates a cache of up to `capacity` entries.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_weigher(capacity, |_, _| 1)
+    }
+
+    /// Creates a cache of up to `capacity` in the total weight of
+    /// the entries, of which weight is given by the `weigher`.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `capacity` is zero.
+    pub fn with_weigher(capacity: usize, weigher: fn(&K, &V) -> usize) -> Self {
+        assert!(capacity > 0, "zero capacity");
+        Self {
+            map: HMap::new(),
+            list: LinkedList::new(),
+            capacity,
+            weight: 0,
+            weigher,
+            on_evict: None,
+            stats: Stats::default(),
+        }
+    }
+
+    /// Sets the callback called with the entries evicted for
+    /// the capacity.
+    pub fn on_evict<F: FnMut(K, V) + 'static>(&mut self, f: F) {
+        self.on_evict = Some(Box::new(f));
+    }
+
+    /// Inserts the key/value as the most recently used entry, evicting
+    /// the least recently used entries over the capacity, and returns
+    /// the old value in case the key was already in the cache.
+    ///
+    /// The entry heavier than the capacity is evicted right away.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        let weight = (self.weigher)(&key, &value);
+        let old = match self.map.get(&key) {
+            Some(&handle) => {
+                self.list.move_to_back(handle);
+                let entry = self.list.get_mut(handle).unwrap();
+                self.weight = self.weight - entry.weight + weight;
+                entry.weight = weight;
+                Some(mem::replace(&mut entry.value, value))
+            }
+            None => {
+                self.list.push_back(Entry {
+                    key: key.clone(),
+                    value,
+                    weight,
+                });
+                self.map.insert(key, self.list.back_handle().unwrap());
+                self.weight += weight;
+                None
+            }
+        };
+        while self.weight > self.capacity {
+            let (key, value) = self.pop_lru().unwrap();
+            self.stats.evictions += 1;
+            if let Some(ref mut f) = self.on_evict {
+                f(key, value);
+            }
+        }
+        old
+    }
+
+    /// Returns the value and makes it the most recently used one.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let handle = self.touch(key)?;
+        self.list.get(handle).map(|entry| &entry.value)
+    }
+
+    /// Returns the mutable value and makes it the most recently used one.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let handle = self.touch(key)?;
+        self.list.get_mut(handle).map(|entry| &mut entry.value)
+    }
+
+    /// Returns the value without changing the order nor the statistics.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let handle = self.map.get(key)?;
+        self.list.get(*handle).map(|entry| &entry.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.map.get(key).is_some()
+    }
+
+    /// Removes the key and returns the value in case it was in the cache.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let handle = self.map.remove(key)?;
+        let entry = self.list.remove(handle)?;
+        self.weight -= entry.weight;
+        Some(entry.value)
+    }
+
+    /// Removes the least recently used entry.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let entry = self.list.pop_front()?;
+        self.map.remove(&entry.key);
+        self.weight -= entry.weight;
+        Some((entry.key, entry.value))
+    }
+
+    /// Returns the least recently used entry without removing it.
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.list.front().map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Returns the handle of the key after counting the lookup.
+    fn touch<Q>(&mut self, key: &Q) -> Option<Handle>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.map.get(key) {
+            Some(&handle) => {
+                self.stats.hits += 1;
+                self.list.move_to_back(handle);
+                Some(handle)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+}
+
+impl<K, V> LruCache<K, V> {
+    pub const fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub const fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the total weight of the entries.
+    pub const fn weight(&self) -> usize {
+        self.weight
+    }
+
+    pub const fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = Stats::default();
+    }
+
+    /// Returns the entries from the most recently used one.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+        self.list
+            .iter()
+            .rev()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,142 @@
+use super::LruCache;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn test_put_get() {
+    let mut cache = LruCache::new(2);
+    assert_eq!(cache.put("a", 1), None);
+    assert_eq!(cache.put("b", 2), None);
+    assert_eq!(cache.get("a"), Some(&1));
+    // evicts "b" as "a" is used more recently.
+    assert_eq!(cache.put("c", 3), None);
+    assert!(!cache.contains_key("b"));
+    assert_eq!(cache.get("b"), None);
+    assert_eq!(cache.put("a", 10), Some(1));
+    assert_eq!(cache.len(), 2);
+    assert!(cache.iter().eq([(&"a", &10), (&"c", &3)]));
+
+    *cache.get_mut("c").unwrap() += 1;
+    assert_eq!(cache.peek_lru(), Some((&"a", &10)));
+    assert_eq!(cache.remove("a"), Some(10));
+    assert_eq!(cache.remove("a"), None);
+    assert_eq!(cache.pop_lru(), Some(("c", 4)));
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_peek() {
+    let mut cache = LruCache::new(2);
+    cache.put(1, "one");
+    cache.put(2, "two");
+    // doesn't promote the entry.
+    assert_eq!(cache.peek(&1), Some(&"one"));
+    cache.put(3, "three");
+    assert_eq!(cache.peek(&1), None);
+    assert_eq!(cache.stats().hits + cache.stats().misses, 0);
+}
+
+#[test]
+fn test_stats() {
+    let mut cache = LruCache::new(2);
+    assert_eq!(cache.stats().hit_ratio(), 0.0);
+    cache.put(1, ());
+    cache.get(&1);
+    cache.get(&1);
+    cache.get(&1);
+    cache.get(&2);
+    cache.put(2, ());
+    cache.put(3, ());
+    assert_eq!(cache.stats().hits, 3);
+    assert_eq!(cache.stats().misses, 1);
+    assert_eq!(cache.stats().evictions, 1);
+    assert_eq!(cache.stats().hit_ratio(), 0.75);
+    cache.reset_stats();
+    assert_eq!(cache.stats().hits, 0);
+}
+
+#[test]
+fn test_on_evict() {
+    let evicted = Rc::new(RefCell::new(Vec::new()));
+    let mut cache = LruCache::new(2);
+    let log = evicted.clone();
+    cache.on_evict(move |k, v| log.borrow_mut().push((k, v)));
+    for k in 0..5 {
+        cache.put(k, k * 10);
+    }
+    // not called on the explicit removals.
+    cache.pop_lru();
+    cache.remove(&4);
+    assert_eq!(*evicted.borrow(), vec![(0, 0), (1, 10), (2, 20)]);
+}
+
+#[test]
+fn test_weigher() {
+    let mut cache = LruCache::with_weigher(10, |_, v: &String| v.len());
+    cache.put(1, "abcd".to_string());
+    cache.put(2, "efgh".to_string());
+    assert_eq!(cache.weight(), 8);
+    cache.put(3, "ij".to_string());
+    assert_eq!(cache.len(), 3);
+    // evicts the first two to make the room.
+    cache.put(4, "klmnop".to_string());
+    assert!(cache.iter().map(|(k, _)| *k).eq([4, 3]));
+    assert_eq!(cache.weight(), 8);
+
+    // shrinks the value in place.
+    cache.put(4, "k".to_string());
+    assert_eq!(cache.weight(), 3);
+    // the entry heavier than the capacity doesn't stay.
+    cache.put(5, "too heavy value".to_string());
+    assert!(cache.is_empty());
+    assert_eq!(cache.weight(), 0);
+}
+
+#[test]
+#[should_panic(expected = "zero capacity")]
+fn test_zero_capacity() {
+    LruCache::<i32, i32>::new(0);
+}
+
+#[test]
+fn test_against_vec_deque() {
+    const CAPACITY: usize = 50;
+
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut cache = LruCache::new(CAPACITY);
+    // the entries from the least recently used.
+    let mut oracle: VecDeque<(i32, i32)> = VecDeque::new();
+
+    for i in 0..10_000 {
+        let k = rng.gen_range(0..100);
+        let pos = oracle.iter().position(|(x, _)| *x == k);
+        match rng.gen_range(0..4) {
+            0 | 1 => {
+                let old = pos.and_then(|pos| oracle.remove(pos)).map(|(_, v)| v);
+                oracle.push_back((k, i));
+                if oracle.len() > CAPACITY {
+                    oracle.pop_front();
+                }
+                assert_eq!(cache.put(k, i), old);
+            }
+            2 => {
+                let want = pos.and_then(|pos| oracle.remove(pos)).map(|entry| {
+                    oracle.push_back(entry);
+                    entry.1
+                });
+                assert_eq!(cache.get(&k).copied(), want);
+            }
+            _ => {
+                let want = pos.and_then(|pos| oracle.remove(pos)).map(|(_, v)| v);
+                assert_eq!(cache.remove(&k), want);
+            }
+        }
+        assert_eq!(cache.len(), oracle.len());
+    }
+    assert!(cache.iter().rev().eq(oracle.iter().map(|(k, v)| (k, v))));
+}
@@ -0,0 +1,72 @@
+//! Benchmarks the ordered map implementations against each other.
+
+use std::collections::BTreeMap;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use b_tree::BTree;
+use balanced_tree::AvlMap;
+use ordered_map::OrderedMap;
+use red_black_tree::RbMap;
+
+const N: u64 = 10_000;
+
+fn keys() -> Vec<u64> {
+    let mut rng = StdRng::seed_from_u64(55);
+    (0..N).map(|_| rng.gen_range(0..N * 10)).collect()
+}
+
+fn filled<M: OrderedMap<u64, u64>>(new: fn() -> M, keys: &[u64]) -> M {
+    let mut map = new();
+    for k in keys {
+        map.insert(*k, *k);
+    }
+    map
+}
+
+fn bench<M: OrderedMap<u64, u64>>(c: &mut Criterion, name: &str, new: fn() -> M) {
+    let keys = keys();
+    let map = filled(new, &keys);
+
+    c.bench_with_input(BenchmarkId::new("insert", name), &keys, |b, keys| {
+        b.iter(|| filled(new, keys))
+    });
+    c.bench_with_input(BenchmarkId::new("get", name), &keys, |b, keys| {
+        b.iter(|| {
+            for k in keys {
+                black_box(map.get(k));
+            }
+        })
+    });
+    c.bench_with_input(BenchmarkId::new("range", name), &keys, |b, keys| {
+        b.iter(|| {
+            for k in keys.iter().take(1_000) {
+                black_box(map.range(*k..*k + 1_000).count());
+            }
+        })
+    });
+    c.bench_with_input(BenchmarkId::new("remove", name), &keys, |b, keys| {
+        b.iter_batched(
+            || filled(new, keys),
+            |mut map| {
+                for k in keys {
+                    black_box(map.remove(k));
+                }
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+}
+
+fn ordered_maps(c: &mut Criterion) {
+    bench(c, "avl", AvlMap::new);
+    bench(c, "red-black", RbMap::new);
+    bench(c, "b-tree", BTree::new);
+    bench(c, "b-tree(32)", || BTree::with_order(32));
+    bench(c, "std", BTreeMap::new);
+}
+
+criterion_group!(benches, ordered_maps);
+criterion_main!(benches);
@@ -0,0 +1,138 @@
+//! A conformance test suite for the [`OrderedMap`] implementations.
+//!
+//! Each implementation enables the `conformance` feature in its
+//! dev-dependencies, and calls [`check`] from its own tests with
+//! the constructor of the empty map, e.g.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! ordered_map::conformance::check(BTreeMap::new);
+//! ```
+//!
+//! It panics at the first behavior different from the `std` `BTreeMap`.
+
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::OrderedMap;
+
+/// Runs all the conformance tests against the map created by `new`.
+pub fn check<M, F>(new: F)
+where
+    M: OrderedMap<i32, i32>,
+    F: Fn() -> M,
+{
+    empty(new());
+    insert_get(new());
+    remove(new());
+    range(new());
+    sequential(new(), 0..2_000);
+    sequential(new(), (0..2_000).rev());
+    for seed in 0..4 {
+        random(new(), seed);
+    }
+}
+
+fn empty<M: OrderedMap<i32, i32>>(mut map: M) {
+    assert!(map.is_empty());
+    assert_eq!(map.len(), 0);
+    assert_eq!(map.get(&1), None);
+    assert_eq!(map.remove(&1), None);
+    assert_eq!(map.range(..).count(), 0);
+}
+
+fn insert_get<M: OrderedMap<i32, i32>>(mut map: M) {
+    for k in [4, 5, 6, 10, 1, 94, 54, 3] {
+        assert_eq!(map.insert(k, k * 10), None);
+    }
+    assert_eq!(map.len(), 8);
+    assert!(!map.is_empty());
+    assert_eq!(map.get(&10), Some(&100));
+    assert_eq!(map.get(&11), None);
+    assert!(map.contains_key(&94));
+    assert!(!map.contains_key(&95));
+
+    assert_eq!(map.insert(10, 1), Some(100));
+    assert_eq!(map.len(), 8);
+    assert_eq!(map.get(&10), Some(&1));
+}
+
+fn remove<M: OrderedMap<i32, i32>>(mut map: M) {
+    for k in [4, 5, 6, 10, 1, 94, 54, 3] {
+        map.insert(k, k * 10);
+    }
+    assert_eq!(map.remove(&5), Some(50));
+    assert_eq!(map.remove(&5), None);
+    assert_eq!(map.remove(&4), Some(40));
+    assert_eq!(map.remove(&94), Some(940));
+    assert_eq!(map.len(), 5);
+    let keys: Vec<_> = map.range(..).map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec![1, 3, 6, 10, 54]);
+
+    for k in keys {
+        assert_eq!(map.remove(&k), Some(k * 10));
+    }
+    assert!(map.is_empty());
+    assert_eq!(map.range(..).count(), 0);
+}
+
+fn range<M: OrderedMap<i32, i32>>(mut map: M) {
+    let mut oracle = BTreeMap::new();
+    for k in (0..100).step_by(3) {
+        map.insert(k, k);
+        oracle.insert(k, k);
+    }
+    let bounds = |k| [Bound::Included(k), Bound::Excluded(k), Bound::Unbounded];
+    for start in -1..102 {
+        for end in start + 1..103 {
+            for r in bounds(start)
+                .into_iter()
+                .flat_map(|s| bounds(end).into_iter().map(move |e| (s, e)))
+            {
+                assert!(
+                    map.range(r).eq(oracle.range(r)),
+                    "range({r:?}) differs from the oracle"
+                );
+            }
+        }
+    }
+}
+
+fn sequential<M, I>(mut map: M, keys: I)
+where
+    M: OrderedMap<i32, i32>,
+    I: Iterator<Item = i32> + Clone,
+{
+    for k in keys.clone() {
+        assert_eq!(map.insert(k, k), None);
+    }
+    assert!(map.range(..).map(|(k, _)| *k).eq(0..2_000));
+    for k in keys.filter(|k| k % 3 != 0) {
+        assert_eq!(map.remove(&k), Some(k));
+    }
+    assert!(map.range(..).map(|(k, _)| *k).eq((0..2_000).step_by(3)));
+}
+
+fn random<M: OrderedMap<i32, i32>>(mut map: M, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut oracle = BTreeMap::new();
+
+    for _ in 0..10_000 {
+        let k = rng.gen_range(0..500);
+        match rng.gen_range(0..4) {
+            0 | 1 => assert_eq!(map.insert(k, k * 10), oracle.insert(k, k * 10)),
+            2 => assert_eq!(map.remove(&k), oracle.remove(&k)),
+            _ => {
+                assert_eq!(map.get(&k), oracle.get(&k));
+                let end = k + rng.gen_range(1..50);
+                assert!(map.range(k..end).eq(oracle.range(k..end)));
+            }
+        }
+        assert_eq!(map.len(), oracle.len());
+    }
+    assert!(map.range(..).eq(oracle.iter()));
+}
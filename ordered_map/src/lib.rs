@@ -0,0 +1,57 @@
+//! An ordered map interface
+
+#![forbid(unsafe_code, missing_debug_implementations)]
+
+use std::collections::BTreeMap;
+use std::ops::RangeBounds;
+
+#[cfg(feature = "conformance")]
+pub mod conformance;
+
+/// A map keeping its entries in the key order.
+pub trait OrderedMap<K, V> {
+    /// Inserts the key/value and returns the old value in case
+    /// the key was already in the map.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Removes the key and returns the value in case it was in the map.
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    /// Returns the iterator over the entries within the range in
+    /// the key order.
+    fn range<R: RangeBounds<K>>(&self, range: R) -> Box<dyn Iterator<Item = (&K, &V)> + '_>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for BTreeMap<K, V> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert(key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+
+    fn range<R: RangeBounds<K>>(&self, range: R) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(self.range(range))
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
@@ -8,8 +8,38 @@ pub fn rand(max: usize) -> usize {
     RG.lock().unwrap().next(max)
 }
 
-#[derive(Debug)]
-struct RandGen {
+/// A source of the random numbers.
+pub trait RandomSource {
+    /// Returns the random number less than `max`.
+    fn rand(&mut self, max: usize) -> usize;
+}
+
+/// The global generator behind [`rand`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+impl RandomSource for Global {
+    fn rand(&mut self, max: usize) -> usize {
+        rand(max)
+    }
+}
+
+impl RandomSource for RandGen {
+    fn rand(&mut self, max: usize) -> usize {
+        self.next(max)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<R: rand_core::RngCore> RandomSource for R {
+    fn rand(&mut self, max: usize) -> usize {
+        (self.next_u64() % max as u64) as usize
+    }
+}
+
+/// A linear congruential generator.
+#[derive(Clone, Debug)]
+pub struct RandGen {
     curr: usize,
     mul: usize,
     inc: usize,
@@ -17,16 +47,20 @@ struct RandGen {
 }
 
 impl RandGen {
-    const fn new(curr: usize) -> Self {
+    /// Creates a generator of the seed, which is reduced by the modulo
+    /// so that the next state doesn't overflow.
+    pub const fn new(seed: usize) -> Self {
+        let modulo = 2325454456;
         Self {
-            curr,
+            curr: seed % modulo,
             mul: 56394237,
             inc: 346423491,
-            modulo: 2325454456,
+            modulo,
         }
     }
 
-    fn next(&mut self, max: usize) -> usize {
+    /// Returns the next number less than `max`.
+    pub fn next(&mut self, max: usize) -> usize {
         self.curr = (self.curr * self.mul + self.inc) % self.modulo;
         self.curr % max
     }
@@ -44,4 +78,14 @@ mod tests {
             assert!(hit.insert(got));
         }
     }
+
+    #[test]
+    fn large_seed() {
+        let mut rng = super::RandGen::new(1 << 40);
+        let mut same = super::RandGen::new((1 << 40) % 2325454456);
+        for _ in 0..100 {
+            assert_eq!(rng.next(10), same.next(10));
+        }
+        super::RandGen::new(usize::MAX).next(10);
+    }
 }
@@ -0,0 +1,382 @@
+//! A red-black tree
+//!
+//! It's a left-leaning red-black tree, where a red link always leans
+//! to the left, as described in [Sedgewick's paper].
+//!
+//! [sedgewick's paper]: https://sedgewick.io/wp-content/themes/sedgewick/papers/2008LLRB.pdf
+
+#![forbid(unsafe_code, missing_debug_implementations)]
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+use ordered_map::OrderedMap;
+
+/// An ordered map based on the red-black tree.
+#[derive(Debug)]
+pub struct RbMap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+impl<K, V> Default for RbMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Display for RbMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        print_depth_first(&self.root, f, 0)
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for RbMap<K, V> {
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert(key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+
+    fn range<R: RangeBounds<K>>(&self, range: R) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(self.range(range))
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K: Ord, V> RbMap<K, V> {
+    /// Inserts the key/value and returns the old value in case
+    /// the key was already in the map.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (mut root, old) = insert(self.root.take(), key, value);
+        root.red = false;
+        self.root = Some(root);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Removes the key and returns the value in case it was in the map.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let mut root = self.root.take()?;
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.red = true;
+        }
+        let (root, value) = remove(root, key);
+        self.root = root.map(|mut root| {
+            root.red = false;
+            root
+        });
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut link = &self.root;
+        while let Some(ref node) = link {
+            match key.cmp(&node.key) {
+                Ordering::Less => link = &node.left,
+                Ordering::Greater => link = &node.right,
+                Ordering::Equal => return Some(&node.value),
+            }
+        }
+        None
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the in-order iterator over the entries within the range.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V> {
+        // the entries from the start bound.
+        let mut stack = Vec::new();
+        let mut link = &self.root;
+        while let Some(ref node) = link {
+            if is_after(range.start_bound(), &node.key) {
+                stack.push(&**node);
+                link = &node.left;
+            } else {
+                link = &node.right;
+            }
+        }
+
+        // the last entry before the end bound.
+        let mut last = None;
+        let mut link = &self.root;
+        while let Some(ref node) = link {
+            if is_before(range.end_bound(), &node.key) {
+                last = Some(&**node);
+                link = &node.right;
+            } else {
+                link = &node.left;
+            }
+        }
+
+        match (stack.last(), last) {
+            (Some(first), Some(last)) if first.key <= last.key => Range {
+                stack,
+                last: Some(last),
+            },
+            _ => Range {
+                stack: Vec::new(),
+                last: None,
+            },
+        }
+    }
+}
+
+impl<K, V> RbMap<K, V> {
+    pub const fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// An in-order iterator over the entries of [`RbMap`] within a range.
+#[derive(Debug)]
+pub struct Range<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+    last: Option<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = self.last?;
+        let node = self.stack.pop()?;
+        if std::ptr::eq(node, last) {
+            self.last = None;
+        } else {
+            let mut link = &node.right;
+            while let Some(ref next) = link {
+                self.stack.push(next);
+                link = &next.left;
+            }
+        }
+        Some((&node.key, &node.value))
+    }
+}
+
+/// Returns `true` when the key is after the start bound.
+fn is_after<K: Ord>(bound: Bound<&K>, key: &K) -> bool {
+    match bound {
+        Bound::Included(start) => key >= start,
+        Bound::Excluded(start) => key > start,
+        Bound::Unbounded => true,
+    }
+}
+
+/// Returns `true` when the key is before the end bound.
+fn is_before<K: Ord>(bound: Bound<&K>, key: &K) -> bool {
+    match bound {
+        Bound::Included(end) => key <= end,
+        Bound::Excluded(end) => key < end,
+        Bound::Unbounded => true,
+    }
+}
+
+#[derive(Debug)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// The color of the link from the parent.
+    red: bool,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> From<(K, V)> for Node<K, V> {
+    fn from((key, value): (K, V)) -> Self {
+        Self {
+            key,
+            value,
+            red: true,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+fn insert<K: Ord, V>(link: Link<K, V>, key: K, value: V) -> (Box<Node<K, V>>, Option<V>) {
+    let mut node = match link {
+        None => return (Box::new(Node::from((key, value))), None),
+        Some(node) => node,
+    };
+    let old = match key.cmp(&node.key) {
+        Ordering::Less => {
+            let (left, old) = insert(node.left.take(), key, value);
+            node.left = Some(left);
+            old
+        }
+        Ordering::Greater => {
+            let (right, old) = insert(node.right.take(), key, value);
+            node.right = Some(right);
+            old
+        }
+        Ordering::Equal => Some(mem::replace(&mut node.value, value)),
+    };
+    (fix_up(node), old)
+}
+
+/// Removes the key, which should be in the tree.
+fn remove<K: Ord, V>(mut node: Box<Node<K, V>>, key: &K) -> (Link<K, V>, V) {
+    let value;
+    if *key < node.key {
+        if !is_red(&node.left) && !is_left_red(&node.left) {
+            node = move_red_left(node);
+        }
+        let (left, removed) = remove(node.left.take().unwrap(), key);
+        node.left = left;
+        value = removed;
+    } else {
+        if is_red(&node.left) {
+            node = rotate_right(node);
+        }
+        if *key == node.key && node.right.is_none() {
+            return (None, node.value);
+        }
+        if !is_red(&node.right) && !is_left_red(&node.right) {
+            node = move_red_right(node);
+        }
+        if *key == node.key {
+            // replaces the node with the next entry.
+            let (right, (k, v)) = remove_min(node.right.take().unwrap());
+            node.right = right;
+            node.key = k;
+            value = mem::replace(&mut node.value, v);
+        } else {
+            let (right, removed) = remove(node.right.take().unwrap(), key);
+            node.right = right;
+            value = removed;
+        }
+    }
+    (Some(fix_up(node)), value)
+}
+
+fn remove_min<K, V>(mut node: Box<Node<K, V>>) -> (Link<K, V>, (K, V)) {
+    if node.left.is_none() {
+        return (None, (node.key, node.value));
+    }
+    if !is_red(&node.left) && !is_left_red(&node.left) {
+        node = move_red_left(node);
+    }
+    let (left, min) = remove_min(node.left.take().unwrap());
+    node.left = left;
+    (Some(fix_up(node)), min)
+}
+
+fn is_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().map(|node| node.red).unwrap_or(false)
+}
+
+/// Returns `true` when the left link of the node is red.
+fn is_left_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref()
+        .map(|node| is_red(&node.left))
+        .unwrap_or(false)
+}
+
+fn fix_up<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    if is_red(&node.right) && !is_red(&node.left) {
+        node = rotate_left(node);
+    }
+    if is_red(&node.left) && is_left_red(&node.left) {
+        node = rotate_right(node);
+    }
+    if is_red(&node.left) && is_red(&node.right) {
+        flip_colors(&mut node);
+    }
+    node
+}
+
+fn move_red_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    flip_colors(&mut node);
+    if is_left_red(&node.right) {
+        node.right = node.right.take().map(rotate_right);
+        node = rotate_left(node);
+        flip_colors(&mut node);
+    }
+    node
+}
+
+fn move_red_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    flip_colors(&mut node);
+    if is_left_red(&node.left) {
+        node = rotate_right(node);
+        flip_colors(&mut node);
+    }
+    node
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    // picks the right node as the new top node.
+    let mut top = node.right.take().unwrap();
+    node.right = top.left.take();
+    top.red = node.red;
+    node.red = true;
+    top.left = Some(node);
+    top
+}
+
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut top = node.left.take().unwrap();
+    node.left = top.right.take();
+    top.red = node.red;
+    node.red = true;
+    top.right = Some(node);
+    top
+}
+
+fn flip_colors<K, V>(node: &mut Node<K, V>) {
+    node.red = !node.red;
+    for child in [&mut node.left, &mut node.right].into_iter().flatten() {
+        child.red = !child.red;
+    }
+}
+
+fn print_depth_first<K: Debug, V: Debug>(
+    link: &Link<K, V>,
+    f: &mut fmt::Formatter<'_>,
+    depth: usize,
+) -> fmt::Result {
+    if let Some(ref node) = link {
+        print_depth_first(&node.left, f, depth + 1)?;
+        let color = if node.red { 'R' } else { 'B' };
+        writeln!(
+            f,
+            "{}:{:.<depth$}{:?}={:?}",
+            color, "", node.key, node.value
+        )?;
+        print_depth_first(&node.right, f, depth + 1)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,56 @@
+use super::{is_red, Link, RbMap};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Checks the red-black tree invariants and returns the black height.
+fn check_invariants<K: Ord, V>(link: &Link<K, V>) -> usize {
+    let node = match link {
+        None => return 1,
+        Some(node) => node,
+    };
+    assert!(!is_red(&node.right), "red right link");
+    assert!(!(node.red && is_red(&node.left)), "two red links in a row");
+    if let Some(ref left) = node.left {
+        assert!(left.key < node.key);
+    }
+    if let Some(ref right) = node.right {
+        assert!(right.key > node.key);
+    }
+    let left = check_invariants(&node.left);
+    let right = check_invariants(&node.right);
+    assert_eq!(left, right, "unbalanced black height");
+    left + usize::from(!node.red)
+}
+
+#[test]
+fn test_ordered_map_conformance() {
+    ordered_map::conformance::check(RbMap::new);
+}
+
+#[test]
+fn test_invariants() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut map = RbMap::new();
+
+    for _ in 0..5_000 {
+        let k = rng.gen_range(0..500);
+        if rng.gen_bool(0.6) {
+            map.insert(k, k);
+        } else {
+            map.remove(&k);
+        }
+        assert!(!is_red(&map.root));
+        check_invariants(&map.root);
+    }
+}
+
+#[test]
+fn test_sequential_height() {
+    let mut map = RbMap::new();
+    for k in 0..1_024 {
+        map.insert(k, ());
+    }
+    // the black height is at most log2(n) + 1.
+    assert!(check_invariants(&map.root) <= 12);
+}
@@ -0,0 +1,226 @@
+//! A binary tree renderer.
+//!
+//! It renders any binary tree implementing [`Render`] either in the
+//! [Graphviz DOT] language with [`dot`] or as the top-down box-drawing
+//! layout with [`boxes`], e.g.
+//!
+//! ```text
+//! ┌───4─┐
+//! 1─┐   5─┐
+//!   3     6──┐
+//!           10─────┐
+//!               ┌─94
+//!              54
+//! ```
+//!
+//! [graphviz dot]: https://graphviz.org/doc/info/lang.html
+
+#![forbid(unsafe_code, missing_debug_implementations)]
+
+use std::fmt::Write;
+
+/// A binary tree node to be rendered.
+pub trait Render {
+    /// The node data.
+    fn label(&self) -> String;
+
+    /// The extra per-node information, e.g. the height or the weight.
+    fn info(&self) -> Option<String> {
+        None
+    }
+
+    fn left(&self) -> Option<&Self>;
+
+    fn right(&self) -> Option<&Self>;
+}
+
+/// Renders the tree in the Graphviz DOT language.
+pub fn dot<N: Render>(root: Option<&N>) -> String {
+    let mut out = String::from("digraph {\n");
+    // the nodes in pre-order, of which edge from the parent is written
+    // after the subtree, with the explicit stack for the deep trees.
+    let mut stack: Vec<DotStep<'_, N>> = root
+        .into_iter()
+        .map(|root| DotStep::Node(root, None))
+        .collect();
+    let mut id = 0;
+    while let Some(step) = stack.pop() {
+        let (node, edge) = match step {
+            DotStep::Node(node, edge) => (node, edge),
+            DotStep::Edge(parent, port, child) => {
+                // writing to the string never fails.
+                let _ = writeln!(out, "    n{parent}:{port} -> n{child};");
+                continue;
+            }
+        };
+        let me = id;
+        id += 1;
+        let label = match node.info() {
+            Some(info) => format!("{}\n{}", node.label(), info),
+            None => node.label(),
+        };
+        let _ = writeln!(out, "    n{me} [label=\"{}\"];", escape(&label));
+        if let Some((parent, port)) = edge {
+            stack.push(DotStep::Edge(parent, port, me));
+        }
+        for (child, port) in [(node.right(), "se"), (node.left(), "sw")] {
+            if let Some(child) = child {
+                stack.push(DotStep::Node(child, Some((me, port))));
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// A step of [`dot`] on the stack.
+enum DotStep<'a, N> {
+    /// The node to write, and the parent ID and port of its edge.
+    Node(&'a N, Option<(usize, &'static str)>),
+    /// The edge from the parent ID and port to the child ID.
+    Edge(usize, &'static str, usize),
+}
+
+/// Escapes the label for the DOT quoted string.
+fn escape(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders the tree top-down with the box-drawing characters.
+///
+/// Each subtree takes the columns of its own, with the node at the top
+/// row and the subtrees side by side below it.  It lays out the nodes
+/// without recursion, and writes each row once.
+pub fn boxes<N: Render>(root: Option<&N>) -> String {
+    let nodes = layout(root);
+    let mut rows: Vec<(String, usize)> = Vec::new();
+    for node in &nodes {
+        if rows.len() == node.depth {
+            rows.push((String::new(), 0));
+        }
+        // the nodes of the same depth come left to right in pre-order.
+        let (row, width) = &mut rows[node.depth];
+        push_repeat(row, " ", node.column - *width);
+        *width = node.column + node.label.chars().count();
+        if let Some(left) = node.left {
+            let left = &nodes[left];
+            push_repeat(row, " ", left.root);
+            row.push('┌');
+            push_repeat(row, "─", left.width - left.root);
+            *width += left.width + 1;
+        }
+        row.push_str(&node.label);
+        if let Some(right) = node.right {
+            let right = &nodes[right];
+            push_repeat(row, "─", right.root + 1);
+            row.push('┐');
+            *width += right.root + 2;
+        }
+    }
+    let mut out = String::new();
+    for (row, _) in rows {
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// A node laid out by [`layout`].
+struct Block {
+    label: String,
+    /// The indices of the children.
+    left: Option<usize>,
+    right: Option<usize>,
+    depth: usize,
+    /// The width of the subtree.
+    width: usize,
+    /// The column of the connector to the subtree from its left.
+    root: usize,
+    /// The column of the subtree.
+    column: usize,
+}
+
+/// Lays out the nodes in pre-order.
+fn layout<N: Render>(root: Option<&N>) -> Vec<Block> {
+    // lists the nodes in pre-order, with the child indices.
+    let mut nodes: Vec<Block> = Vec::new();
+    // the nodes with the depth and the parent index, of which left child
+    // is popped first for the pre-order.
+    let mut stack: Vec<_> = root
+        .into_iter()
+        .map(|root| (root, 0, None::<(usize, bool)>))
+        .collect();
+    while let Some((node, depth, parent)) = stack.pop() {
+        let index = nodes.len();
+        match parent {
+            Some((parent, true)) => nodes[parent].left = Some(index),
+            Some((parent, false)) => nodes[parent].right = Some(index),
+            None => {}
+        }
+        let label = match node.info() {
+            Some(info) => format!("{} [{}]", node.label(), info),
+            None => node.label(),
+        };
+        nodes.push(Block {
+            label,
+            left: None,
+            right: None,
+            depth,
+            width: 0,
+            root: 0,
+            column: 0,
+        });
+        if let Some(right) = node.right() {
+            stack.push((right, depth + 1, Some((index, false))));
+        }
+        if let Some(left) = node.left() {
+            stack.push((left, depth + 1, Some((index, true))));
+        }
+    }
+
+    // sizes the subtrees bottom-up, as the children come after the parent.
+    for i in (0..nodes.len()).rev() {
+        let label_width = nodes[i].label.chars().count();
+        let left = nodes[i].left.map(|left| nodes[left].width + 1).unwrap_or(0);
+        let right = nodes[i]
+            .right
+            .map(|right| nodes[right].width + 1)
+            .unwrap_or(0);
+        let node = &mut nodes[i];
+        node.width = left + label_width + right;
+        node.root = left + label_width / 2;
+    }
+    // places the subtrees top-down.
+    for i in 0..nodes.len() {
+        let column = nodes[i].column;
+        let label_width = nodes[i].label.chars().count();
+        let mut right_column = column + label_width + 1;
+        if let Some(left) = nodes[i].left {
+            nodes[left].column = column;
+            right_column += nodes[left].width + 1;
+        }
+        if let Some(right) = nodes[i].right {
+            nodes[right].column = right_column;
+        }
+    }
+    nodes
+}
+
+/// Appends the `s` repeated `n` times.
+fn push_repeat(out: &mut String, s: &str, n: usize) {
+    for _ in 0..n {
+        out.push_str(s);
+    }
+}
+
+#[cfg(test)]
+mod test;
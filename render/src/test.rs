@@ -0,0 +1,135 @@
+use super::{boxes, dot, Render};
+
+#[derive(Debug)]
+struct Node {
+    data: &'static str,
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        // unlinks the children not to recurse on the deep trees.
+        let mut stack: Vec<_> = self
+            .left
+            .take()
+            .into_iter()
+            .chain(self.right.take())
+            .collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+impl Render for Node {
+    fn label(&self) -> String {
+        self.data.to_string()
+    }
+
+    fn info(&self) -> Option<String> {
+        (self.data == "4").then(|| "root".to_string())
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+fn node(data: &'static str, left: Option<Node>, right: Option<Node>) -> Option<Node> {
+    Some(Node {
+        data,
+        left: left.map(Box::new),
+        right: right.map(Box::new),
+    })
+}
+
+//     4
+//    / \
+//   1   50
+//    \
+//     "3"
+fn tree() -> Node {
+    let three = node("\"3\"", None, None);
+    let one = node("1", None, three);
+    let fifty = node("50", None, None);
+    node("4", one, fifty).unwrap()
+}
+
+#[test]
+fn test_dot() {
+    assert_eq!(dot::<Node>(None), "digraph {\n}\n");
+    assert_eq!(
+        dot(Some(&tree())),
+        "digraph {
+    n0 [label=\"4\\nroot\"];
+    n1 [label=\"1\"];
+    n2 [label=\"\\\"3\\\"\"];
+    n1:se -> n2;
+    n0:sw -> n1;
+    n3 [label=\"50\"];
+    n0:se -> n3;
+}
+"
+    );
+}
+
+#[test]
+fn test_boxes() {
+    assert_eq!(boxes::<Node>(None), "");
+    assert_eq!(boxes(node("x", None, None).as_ref()), "x\n");
+    assert_eq!(
+        boxes(Some(&tree())),
+        "\
+┌─────4 [root]──┐
+1──┐           50
+  \"3\"
+"
+    );
+}
+
+#[test]
+fn test_boxes_deep() {
+    let mut tree = node("0", None, None);
+    for data in ["1", "2", "3"] {
+        tree = node(data, tree, None);
+    }
+    assert_eq!(
+        boxes(tree.as_ref()),
+        "    ┌─3
+  ┌─2
+┌─1
+0
+"
+    );
+}
+
+#[test]
+fn test_degenerate() {
+    // the zigzag path, of which right child goes left.
+    let mut tree = node("x", None, None);
+    for i in 0..200_000 {
+        tree = match i % 2 {
+            0 => node("x", tree, None),
+            _ => node("x", None, tree),
+        };
+    }
+    let dot = dot(tree.as_ref());
+    assert_eq!(dot.lines().count(), 2 + 200_001 * 2 - 1);
+    assert!(dot.ends_with("    n0:se -> n1;\n}\n"));
+
+    // keeps the picture, of which rows grow with the depth, small.
+    let mut tree = node("0", None, None);
+    for _ in 0..5_000 {
+        tree = node("x", None, tree);
+    }
+    let boxes = boxes(tree.as_ref());
+    assert_eq!(boxes.lines().count(), 5_001);
+    assert!(boxes.starts_with("x─┐\n  x─┐\n"));
+    assert!(boxes.ends_with(&format!("{}0\n", " ".repeat(5_000 * 2))));
+}
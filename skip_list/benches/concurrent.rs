@@ -0,0 +1,98 @@
+//! Benchmarks the concurrent skip list against the mutex-wrapped `BTreeMap`.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::thread;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use skip_list::ConcurrentSkipList;
+
+// Operations per thread.
+const OPS: usize = 10_000;
+
+// Range of the keys.
+const KEYS: u64 = 10_000;
+
+/// The shared map under the benchmarks.
+trait Shared: Sync {
+    fn insert(&self, key: u64, value: u64);
+    fn get(&self, key: &u64) -> Option<u64>;
+    fn remove(&self, key: &u64);
+}
+
+impl Shared for ConcurrentSkipList<u64, u64> {
+    fn insert(&self, key: u64, value: u64) {
+        ConcurrentSkipList::insert(self, key, value);
+    }
+
+    fn get(&self, key: &u64) -> Option<u64> {
+        ConcurrentSkipList::get(self, key)
+    }
+
+    fn remove(&self, key: &u64) {
+        ConcurrentSkipList::remove(self, key);
+    }
+}
+
+impl Shared for Mutex<BTreeMap<u64, u64>> {
+    fn insert(&self, key: u64, value: u64) {
+        self.lock().unwrap().insert(key, value);
+    }
+
+    fn get(&self, key: &u64) -> Option<u64> {
+        self.lock().unwrap().get(key).copied()
+    }
+
+    fn remove(&self, key: &u64) {
+        self.lock().unwrap().remove(key);
+    }
+}
+
+/// Runs the operations of which `reads` out of 10 are lookups, and
+/// the rest are inserts and removes half-and-half.
+fn run<M: Shared>(map: &M, threads: usize, reads: u32) {
+    thread::scope(|s| {
+        for t in 0..threads {
+            s.spawn(move || {
+                let mut rng = StdRng::seed_from_u64(t as u64);
+                for _ in 0..OPS {
+                    let k = rng.gen_range(0..KEYS);
+                    match rng.gen_range(0..10) {
+                        n if n < reads => {
+                            black_box(map.get(&k));
+                        }
+                        n if n % 2 == 0 => map.insert(k, k),
+                        _ => map.remove(&k),
+                    }
+                }
+            });
+        }
+    });
+}
+
+fn bench<M: Shared>(c: &mut Criterion, name: &str, new: fn() -> M) {
+    let map = new();
+    for k in (0..KEYS).step_by(2) {
+        map.insert(k, k);
+    }
+    for threads in [1, 4, 8] {
+        for (workload, reads) in [("read-heavy", 9), ("write-heavy", 2)] {
+            c.bench_with_input(
+                BenchmarkId::new(format!("{workload}/{name}"), threads),
+                &threads,
+                |b, &threads| b.iter(|| run(&map, threads, reads)),
+            );
+        }
+    }
+}
+
+fn concurrent(c: &mut Criterion) {
+    bench(c, "skip_list", ConcurrentSkipList::new);
+    bench(c, "mutex_btree_map", || Mutex::new(BTreeMap::new()));
+}
+
+criterion_group!(benches, concurrent);
+criterion_main!(benches);
@@ -0,0 +1,162 @@
+//! The skip list of the `Rc<RefCell>` nodes
+//!
+//! It's the [`SkipList`](skip_list::SkipList) as it was before the nodes
+//! moved into the arena, kept as the baseline of the benchmarks.  Each
+//! node is allocated on its own with the `Vec` of the next nodes, and
+//! the lookups clone the `Rc` of each node on the way.
+
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+
+use skip_list::{Global, RandomSource};
+
+// Default maximum number of levels.
+const MAX_LEVEL: usize = 16;
+
+// Default probability to promote the node to the next level.
+const P: f64 = 0.5;
+
+// Resolution of the promotion probability.
+const PRECISION: usize = 1 << 16;
+
+pub struct SkipList<K, V> {
+    /// The first node of each level.
+    head: Vec<Link<K, V>>,
+    rng: Global,
+}
+
+type Link<K, V> = Option<Rc<RefCell<Node<K, V>>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// The next node on each level of the node.
+    next: Vec<Link<K, V>>,
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    pub const fn new() -> Self {
+        Self {
+            head: Vec::new(),
+            rng: Global,
+        }
+    }
+
+    /// Inserts the key/value and returns the old value in case
+    /// the key was already in the list.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut prevs = self.prevs(&key);
+        if let Some(node) = self.next(&prevs[0], 0) {
+            let mut node = node.borrow_mut();
+            if node.key == key {
+                return Some(mem::replace(&mut node.value, value));
+            }
+        }
+        let level = self.random_level();
+        if level > self.head.len() {
+            prevs.resize(level, None);
+            self.head.resize(level, None);
+        }
+        let node = Rc::new(RefCell::new(Node {
+            key,
+            value,
+            next: vec![None; level],
+        }));
+        for (i, prev) in prevs.iter().enumerate().take(level) {
+            let next = self.set_next(prev, i, Some(node.clone()));
+            node.borrow_mut().next[i] = next;
+        }
+        None
+    }
+
+    /// Removes the key and returns the value in case it was in the list.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let prevs = self.prevs(key);
+        let node = self.next(&prevs[0], 0)?;
+        if RefCell::borrow(&node).key.borrow() != key {
+            return None;
+        }
+        let level = RefCell::borrow(&node).next.len();
+        for (i, prev) in prevs.iter().enumerate().take(level) {
+            let next = node.borrow_mut().next[i].take();
+            self.set_next(prev, i, next);
+        }
+        while let Some(None) = self.head.last() {
+            self.head.pop();
+        }
+        // no other links to the node now.
+        Rc::try_unwrap(node)
+            .ok()
+            .map(|node| node.into_inner().value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        V: Clone,
+    {
+        let node = self.next(&self.prevs(key)[0], 0)?;
+        let node = RefCell::borrow(&node);
+        if node.key.borrow() == key {
+            Some(node.value.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the last node before the key on each level, where `None`
+    /// is the head, with at least one level even for the empty list.
+    fn prevs<Q>(&self, key: &Q) -> Vec<Link<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut prevs = vec![None; std::cmp::max(self.head.len(), 1)];
+        let mut prev: Link<K, V> = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(next) = self.next(&prev, level) {
+                if RefCell::borrow(&next).key.borrow() >= key {
+                    break;
+                }
+                prev = Some(next);
+            }
+            prevs[level] = prev.clone();
+        }
+        prevs
+    }
+
+    /// Returns the next node of the `prev` on the level.
+    fn next(&self, prev: &Link<K, V>, level: usize) -> Link<K, V> {
+        match prev {
+            Some(prev) => RefCell::borrow(prev).next[level].clone(),
+            None => self.head.get(level).cloned().flatten(),
+        }
+    }
+
+    /// Replaces the next node of the `prev` on the level and returns
+    /// the old one.
+    fn set_next(&mut self, prev: &Link<K, V>, level: usize, next: Link<K, V>) -> Link<K, V> {
+        match prev {
+            Some(prev) => mem::replace(&mut prev.borrow_mut().next[level], next),
+            None => mem::replace(&mut self.head[level], next),
+        }
+    }
+
+    /// Returns the random level, promoted to the next level with
+    /// the probability `P`.
+    fn random_level(&mut self) -> usize {
+        let threshold = (P * PRECISION as f64) as usize;
+        let mut level = 1;
+        while level < MAX_LEVEL && self.rng.rand(PRECISION) < threshold {
+            level += 1;
+        }
+        level
+    }
+}
@@ -0,0 +1,169 @@
+//! Benchmarks the skip list against its `Rc<RefCell>` version and
+//! `BTreeMap`, and reports the heap usage of each.
+//!
+//! Of the 10k random keys, the arena list takes about the same bytes per
+//! entry as the `Rc` one (79 vs 76), but almost no allocation per insert
+//! against about 3, and is about 1.8 times as fast to insert, 1.2 to get
+//! and 1.5 to remove:
+//!
+//! | list       | insert | get    | remove |
+//! |------------|--------|--------|--------|
+//! | arena      | 3.7 ms | 3.6 ms | 3.6 ms |
+//! | `Rc`       | 6.6 ms | 4.4 ms | 5.4 ms |
+//! | `BTreeMap` | 1.8 ms | 1.2 ms | 1.7 ms |
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use skip_list::SkipList;
+
+mod rc;
+
+const N: u64 = 10_000;
+
+/// The system allocator counting the allocated bytes.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// The maps under the benchmarks.
+trait List {
+    fn new() -> Self;
+    fn insert(&mut self, key: u64, value: u64);
+    fn get(&self, key: &u64) -> Option<u64>;
+    fn remove(&mut self, key: &u64);
+}
+
+impl List for SkipList<u64, u64> {
+    fn new() -> Self {
+        SkipList::new()
+    }
+
+    fn insert(&mut self, key: u64, value: u64) {
+        SkipList::insert(self, key, value);
+    }
+
+    fn get(&self, key: &u64) -> Option<u64> {
+        SkipList::get(self, key).copied()
+    }
+
+    fn remove(&mut self, key: &u64) {
+        SkipList::remove(self, key);
+    }
+}
+
+impl List for rc::SkipList<u64, u64> {
+    fn new() -> Self {
+        rc::SkipList::new()
+    }
+
+    fn insert(&mut self, key: u64, value: u64) {
+        rc::SkipList::insert(self, key, value);
+    }
+
+    fn get(&self, key: &u64) -> Option<u64> {
+        rc::SkipList::get(self, key)
+    }
+
+    fn remove(&mut self, key: &u64) {
+        rc::SkipList::remove(self, key);
+    }
+}
+
+impl List for BTreeMap<u64, u64> {
+    fn new() -> Self {
+        BTreeMap::new()
+    }
+
+    fn insert(&mut self, key: u64, value: u64) {
+        BTreeMap::insert(self, key, value);
+    }
+
+    fn get(&self, key: &u64) -> Option<u64> {
+        BTreeMap::get(self, key).copied()
+    }
+
+    fn remove(&mut self, key: &u64) {
+        BTreeMap::remove(self, key);
+    }
+}
+
+fn keys() -> Vec<u64> {
+    let mut rng = StdRng::seed_from_u64(55);
+    (0..N).map(|_| rng.gen_range(0..N * 10)).collect()
+}
+
+fn filled<L: List>(keys: &[u64]) -> L {
+    let mut list = L::new();
+    for k in keys {
+        list.insert(*k, *k);
+    }
+    list
+}
+
+fn bench<L: List>(c: &mut Criterion, name: &str) {
+    let keys = keys();
+    let (bytes, allocations) = (
+        ALLOCATED.load(Ordering::Relaxed),
+        ALLOCATIONS.load(Ordering::Relaxed),
+    );
+    let list = filled::<L>(&keys);
+    println!(
+        "{name}: {} bytes per entry, {} allocations per insert",
+        (ALLOCATED.load(Ordering::Relaxed) - bytes) as f64 / N as f64,
+        (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / N as f64,
+    );
+
+    c.bench_with_input(BenchmarkId::new("insert", name), &keys, |b, keys| {
+        b.iter(|| filled::<L>(keys))
+    });
+    c.bench_with_input(BenchmarkId::new("get", name), &keys, |b, keys| {
+        b.iter(|| {
+            for k in keys {
+                black_box(list.get(k));
+            }
+        })
+    });
+    c.bench_with_input(BenchmarkId::new("remove", name), &keys, |b, keys| {
+        b.iter_batched(
+            || filled::<L>(keys),
+            |mut list| {
+                for k in keys {
+                    list.remove(k);
+                }
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+}
+
+fn skip_list(c: &mut Criterion) {
+    bench::<SkipList<u64, u64>>(c, "skip_list");
+    bench::<rc::SkipList<u64, u64>>(c, "rc_skip_list");
+    bench::<BTreeMap<u64, u64>>(c, "btree_map");
+}
+
+criterion_group!(benches, skip_list);
+criterion_main!(benches);
@@ -0,0 +1,325 @@
+//! A lock-free concurrent skip list
+//!
+//! It's the lock-free skip list in [The Art of Multiprocessor Programming],
+//! of which nodes are removed by marking the lowest bit of their next
+//! pointers, first logically and then physically by any thread passing by.
+//! The removed nodes are reclaimed through the [`crossbeam_epoch`]
+//! epoch-based garbage collector once they are unlinked from all levels.
+//!
+//! [the art of multiprocessor programming]: https://dl.acm.org/doi/book/10.5555/2385452
+
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
+use std::sync::atomic::{AtomicIsize, AtomicUsize};
+
+use crossbeam_epoch::{self as epoch, Atomic, Guard, Owned, Shared};
+
+// Maximum number of levels.
+const MAX_LEVEL: usize = 32;
+
+/// A skip list shared across threads without any lock.
+///
+/// The values can't be changed once inserted, as the readers may see
+/// them at any time.
+#[derive(Debug)]
+pub struct ConcurrentSkipList<K, V> {
+    /// The first node of each level.
+    head: Box<[Atomic<Node<K, V>>]>,
+    /// The number of entries, which may be below zero for a while when
+    /// a remove decrements it before the insert increments it.
+    len: AtomicIsize,
+}
+
+impl<K, V> Default for ConcurrentSkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for ConcurrentSkipList<K, V> {
+    fn drop(&mut self) {
+        // no other threads access the list any more, and the nodes
+        // unlinked from all the levels are already in the collector,
+        // but a removed one may be still linked on the upper levels.
+        unsafe {
+            let guard = epoch::unprotected();
+            let mut seen = HashSet::new();
+            let mut nodes = Vec::new();
+            for (level, head) in self.head.iter().enumerate() {
+                let mut link = head.load(Relaxed, guard);
+                while let Some(node) = link.as_ref() {
+                    if seen.insert(link.as_raw()) {
+                        nodes.push(link);
+                    }
+                    link = node.next[level].load(Relaxed, guard).with_tag(0);
+                }
+            }
+            for node in nodes {
+                drop(node.into_owned());
+            }
+        }
+    }
+}
+
+impl<K, V> ConcurrentSkipList<K, V> {
+    pub fn new() -> Self {
+        Self {
+            head: (0..MAX_LEVEL).map(|_| Atomic::null()).collect(),
+            len: AtomicIsize::new(0),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of entries.
+    ///
+    /// It's only a snapshot while other threads are updating the list.
+    pub fn len(&self) -> usize {
+        std::cmp::max(self.len.load(Relaxed), 0) as usize
+    }
+}
+
+impl<K, V> ConcurrentSkipList<K, V>
+where
+    K: Ord + Send + 'static,
+    V: Send + 'static,
+{
+    /// Inserts the key/value and returns `true` in case the key was not
+    /// in the list.
+    pub fn insert(&self, key: K, value: V) -> bool {
+        let guard = &epoch::pin();
+        let level = random_level();
+        let mut node = Owned::new(Node {
+            key,
+            value,
+            // the builder and the lowest level.
+            links: AtomicUsize::new(2),
+            next: (0..level).map(|_| Atomic::null()).collect(),
+        });
+
+        // links the node on the lowest level first.
+        let (node, mut pos) = loop {
+            let pos = self.find(&node.key, guard);
+            if pos.found(&node.key).is_some() {
+                return false;
+            }
+            for (next, succ) in node.next.iter().zip(pos.succs) {
+                next.store(succ, Relaxed);
+            }
+            match pos.preds[0].compare_exchange(pos.succs[0], node, Release, Relaxed, guard) {
+                Ok(node) => break (node, pos),
+                Err(err) => node = err.new,
+            }
+        };
+        self.len.fetch_add(1, Relaxed);
+
+        // then builds the tower unless it's removed in the meantime.
+        let n = unsafe { node.deref() };
+        'build: for level in 1..level {
+            loop {
+                let next = n.next[level].load(Acquire, guard);
+                if next.tag() == 1 {
+                    break 'build;
+                }
+                if next != pos.succs[level]
+                    && n.next[level]
+                        .compare_exchange(next, pos.succs[level], AcqRel, Acquire, guard)
+                        .is_err()
+                {
+                    break 'build;
+                }
+                n.links.fetch_add(1, Relaxed);
+                if pos.preds[level]
+                    .compare_exchange(pos.succs[level], node, AcqRel, Acquire, guard)
+                    .is_ok()
+                {
+                    break;
+                }
+                n.links.fetch_sub(1, Relaxed);
+                pos = self.find(&n.key, guard);
+                if pos.succs[0] != node {
+                    break 'build;
+                }
+            }
+        }
+
+        // unlinks the tower by itself in case it's removed while building.
+        if n.next[0].load(Acquire, guard).tag() == 1 {
+            self.find(&n.key, guard);
+        }
+        unsafe { release(node, guard) };
+        true
+    }
+
+    /// Removes the key and returns `true` in case it was in the list.
+    pub fn remove<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let guard = &epoch::pin();
+        let node = match self.find(key, guard).found(key) {
+            Some(node) => node,
+            None => return false,
+        };
+        // marks the tower top-down, and the lowest level last, which
+        // removes the node logically.
+        for next in node.next[1..].iter().rev() {
+            next.fetch_or(1, AcqRel, guard);
+        }
+        if node.next[0].fetch_or(1, AcqRel, guard).tag() == 1 {
+            // removed by other thread.
+            return false;
+        }
+        self.len.fetch_sub(1, Relaxed);
+        self.find(key, guard);
+        true
+    }
+
+    /// Returns the clone of the value.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        V: Clone,
+    {
+        let guard = &epoch::pin();
+        self.find(key, guard)
+            .found(key)
+            .map(|node| node.value.clone())
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let guard = &epoch::pin();
+        self.find(key, guard).found(key).is_some()
+    }
+
+    /// Calls `f` on each entry in the key order.
+    ///
+    /// It may or may not see the entries inserted or removed by other
+    /// threads in the meantime.
+    pub fn for_each<F: FnMut(&K, &V)>(&self, mut f: F) {
+        let guard = &epoch::pin();
+        let mut link = self.head[0].load(Acquire, guard);
+        while let Some(node) = unsafe { link.as_ref() } {
+            let next = node.next[0].load(Acquire, guard);
+            if next.tag() == 0 {
+                f(&node.key, &node.value);
+            }
+            link = next.with_tag(0);
+        }
+    }
+
+    /// Returns the last link before the key and the next node on each
+    /// level, unlinking the removed nodes on the way.
+    fn find<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Position<'g, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        'retry: loop {
+            let mut pos = Position {
+                preds: [&self.head[0]; MAX_LEVEL],
+                succs: [Shared::null(); MAX_LEVEL],
+            };
+            let mut prev: &'g [Atomic<Node<K, V>>] = &self.head;
+            for level in (0..MAX_LEVEL).rev() {
+                let mut link = prev[level].load(Acquire, guard);
+                if link.tag() == 1 {
+                    // the previous node is being removed.
+                    continue 'retry;
+                }
+                while let Some(node) = unsafe { link.as_ref() } {
+                    let next = node.next[level].load(Acquire, guard);
+                    if next.tag() == 1 {
+                        let next = next.with_tag(0);
+                        match prev[level].compare_exchange(link, next, AcqRel, Acquire, guard) {
+                            Ok(_) => unsafe { release(link, guard) },
+                            Err(_) => continue 'retry,
+                        }
+                        link = next;
+                    } else if node.key.borrow() < key {
+                        prev = &node.next;
+                        link = next;
+                    } else {
+                        break;
+                    }
+                }
+                pos.preds[level] = &prev[level];
+                pos.succs[level] = link;
+            }
+            return pos;
+        }
+    }
+}
+
+/// A position in the list found by [`ConcurrentSkipList::find`].
+struct Position<'g, K, V> {
+    preds: [&'g Atomic<Node<K, V>>; MAX_LEVEL],
+    succs: [Shared<'g, Node<K, V>>; MAX_LEVEL],
+}
+
+impl<'g, K, V> Position<'g, K, V> {
+    /// Returns the node of the key.
+    fn found<Q>(&self, key: &Q) -> Option<&'g Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        unsafe { self.succs[0].as_ref() }.filter(|node| node.key.borrow() == key)
+    }
+}
+
+#[derive(Debug)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// The number of the levels linked to the node, plus one while
+    /// the inserting thread builds the tower.
+    links: AtomicUsize,
+    /// The next node on each level of the node, of which lowest bit
+    /// marks the node removed on the level.
+    next: Box<[Atomic<Self>]>,
+}
+
+/// Drops the reference to the node and reclaims it with the last one.
+///
+/// # Safety
+///
+/// The caller should own the reference, e.g. just unlinked the node.
+unsafe fn release<K, V>(node: Shared<'_, Node<K, V>>, guard: &Guard) {
+    if node.deref().links.fetch_sub(1, AcqRel) == 1 {
+        guard.defer_destroy(node);
+    }
+}
+
+/// Returns the random level with the probability of 1/2 for each
+/// promotion from the per-thread xorshift generator.
+fn random_level() -> usize {
+    thread_local! {
+        static STATE: Cell<u64> = const { Cell::new(0) };
+    }
+    STATE.with(|state| {
+        let mut x = state.get();
+        if x == 0 {
+            // seeds with the address unique to the thread.
+            x = state as *const _ as u64 | 1;
+        }
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+        std::cmp::min(x.trailing_ones() as usize + 1, MAX_LEVEL)
+    })
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,242 @@
+use super::ConcurrentSkipList;
+
+use std::collections::BTreeMap;
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Release};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use crossbeam_epoch as epoch;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn entries<K, V>(list: &ConcurrentSkipList<K, V>) -> Vec<(K, V)>
+where
+    K: Ord + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    let mut entries = Vec::new();
+    list.for_each(|k, v| entries.push((k.clone(), v.clone())));
+    entries
+}
+
+#[test]
+fn test_insert_get_remove() {
+    let list = ConcurrentSkipList::new();
+    assert!(list.is_empty());
+    assert!(list.insert("keith".to_string(), 18));
+    assert!(list.insert("dave".to_string(), 1));
+    assert!(list.insert("andy".to_string(), 9));
+    assert!(!list.insert("dave".to_string(), 2));
+
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.get("dave"), Some(1));
+    assert!(list.remove("dave"));
+    assert!(!list.remove("dave"));
+    assert_eq!(list.get("dave"), None);
+    assert!(list.contains_key("keith"));
+    assert_eq!(
+        entries(&list),
+        vec![("andy".to_string(), 9), ("keith".to_string(), 18)]
+    );
+}
+
+#[test]
+fn test_against_btree_map() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let list = ConcurrentSkipList::new();
+    let mut map = BTreeMap::new();
+
+    for i in 0..10_000 {
+        let k = rng.gen_range(0..1_000);
+        if rng.gen_bool(0.4) {
+            assert_eq!(list.remove(&k), map.remove(&k).is_some());
+        } else {
+            let inserted = !map.contains_key(&k);
+            if inserted {
+                map.insert(k, i);
+            }
+            assert_eq!(list.insert(k, i), inserted);
+        }
+        assert_eq!(list.get(&k), map.get(&k).copied());
+    }
+    assert_eq!(list.len(), map.len());
+    assert_eq!(entries(&list), map.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_drop_values() {
+    let value = Arc::new(());
+    let list = ConcurrentSkipList::new();
+    for k in 0..1_000 {
+        list.insert(k, value.clone());
+    }
+    assert_eq!(Arc::strong_count(&value), 1_001);
+    drop(list);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_drop_half_removed_node() {
+    let value = Arc::new(());
+    let list = ConcurrentSkipList::new();
+    for k in 0..100 {
+        list.insert(k, value.clone());
+    }
+    // removes the first node of more than one level, but unlinks it only
+    // from the lowest level as an interrupted remove would.
+    let guard = &epoch::pin();
+    let mut prev = &list.head[0];
+    let node = loop {
+        let node = unsafe { prev.load(Acquire, guard).deref() };
+        if node.next.len() > 1 {
+            break node;
+        }
+        prev = &node.next[0];
+    };
+    for next in node.next.iter() {
+        next.fetch_or(1, AcqRel, guard);
+    }
+    prev.store(node.next[0].load(Acquire, guard).with_tag(0), Release);
+    node.links.fetch_sub(1, AcqRel);
+    drop(list);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_len_below_zero() {
+    let list = ConcurrentSkipList::<i32, i32>::new();
+    // a remove may decrement the length before the insert increments it.
+    list.len.fetch_sub(1, Ordering::Relaxed);
+    assert_eq!(list.len(), 0);
+    assert!(list.is_empty());
+    list.len.fetch_add(2, Ordering::Relaxed);
+    assert_eq!(list.len(), 1);
+}
+
+#[test]
+fn test_lots_of_numbers_across_threads() {
+    const THREADS: usize = 8;
+    const N: usize = 5_000;
+
+    let list = ConcurrentSkipList::new();
+    thread::scope(|s| {
+        for t in 0..THREADS {
+            let list = &list;
+            s.spawn(move || {
+                // interleaves the keys of the threads.
+                for x in 0..N {
+                    assert!(list.insert(x * THREADS + t, t));
+                }
+            });
+        }
+    });
+
+    assert_eq!(list.len(), THREADS * N);
+    let keys: Vec<_> = entries(&list).into_iter().map(|(k, _)| k).collect();
+    assert_eq!(keys, (0..THREADS * N).collect::<Vec<_>>());
+
+    // removes the even keys while the other threads are reading
+    // the odd ones.
+    thread::scope(|s| {
+        for t in 0..THREADS {
+            let list = &list;
+            s.spawn(move || {
+                for x in 0..N {
+                    let k = x * THREADS + t;
+                    if k % 2 == 1 {
+                        assert_eq!(list.get(&k), Some(t));
+                    } else {
+                        assert!(list.remove(&k));
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(list.len(), THREADS * N / 2);
+    for k in 0..THREADS * N {
+        assert_eq!(list.contains_key(&k), k % 2 == 1);
+    }
+}
+
+#[test]
+fn test_races_on_same_keys() {
+    const THREADS: usize = 8;
+    const KEYS: usize = 500;
+    const ROUNDS: usize = 20;
+
+    let list = ConcurrentSkipList::new();
+    let barrier = Barrier::new(THREADS);
+    for _ in 0..ROUNDS {
+        // exactly one thread wins each key on insert and on remove.
+        let inserted = AtomicUsize::new(0);
+        let removed = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let (list, barrier) = (&list, &barrier);
+                let (inserted, removed) = (&inserted, &removed);
+                s.spawn(move || {
+                    barrier.wait();
+                    for k in 0..KEYS {
+                        if list.insert(k, t) {
+                            inserted.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    barrier.wait();
+                    for k in (0..KEYS).rev() {
+                        if list.remove(&k) {
+                            removed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(inserted.into_inner(), KEYS);
+        assert_eq!(removed.into_inner(), KEYS);
+        assert!(list.is_empty());
+        assert!(entries(&list).is_empty());
+    }
+}
+
+#[test]
+fn test_mixed_workload() {
+    const THREADS: u64 = 8;
+    const N: usize = 20_000;
+
+    // each thread owns the keys of its remainder, and checks them against
+    // its own map while the others are churning the neighbours.
+    let list = ConcurrentSkipList::new();
+    let maps: Vec<_> = thread::scope(|s| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let list = &list;
+                s.spawn(move || {
+                    let mut rng = StdRng::seed_from_u64(55 + t);
+                    let mut map = BTreeMap::new();
+                    for i in 0..N {
+                        let k = rng.gen_range(0..200) * THREADS + t;
+                        match rng.gen_range(0..3) {
+                            0 => assert_eq!(list.remove(&k), map.remove(&k).is_some()),
+                            1 => {
+                                let inserted = !map.contains_key(&k);
+                                if inserted {
+                                    map.insert(k, i);
+                                }
+                                assert_eq!(list.insert(k, i), inserted);
+                            }
+                            _ => assert_eq!(list.get(&k), map.get(&k).copied()),
+                        }
+                    }
+                    map
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut want: Vec<_> = maps.into_iter().flatten().collect();
+    want.sort_unstable();
+    assert_eq!(list.len(), want.len());
+    assert_eq!(entries(&list), want);
+}
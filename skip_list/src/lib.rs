@@ -0,0 +1,445 @@
+//! A skip list
+
+#![deny(unsafe_code)]
+#![forbid(missing_debug_implementations)]
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug, Display};
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+pub use concurrent::ConcurrentSkipList;
+pub use random::{Global, RandGen, RandomSource};
+
+#[allow(unsafe_code)]
+mod concurrent;
+
+// Default maximum number of levels.
+const MAX_LEVEL: usize = 16;
+
+// Default probability to promote the node to the next level.
+const P: f64 = 0.5;
+
+// Resolution of the promotion probability.
+const PRECISION: usize = 1 << 16;
+
+// The null link, e.g. the end of the level.
+const NIL: usize = usize::MAX;
+
+/// An ordered map based on the skip list.
+///
+/// The nodes live in a single `Vec` and link each other by the index, and
+/// their towers are the slices of another `Vec` shared by all the nodes.
+/// The removed nodes are kept in the free list of their level to be
+/// reused by the next insert of the same level.
+///
+/// The levels of the nodes are drawn from the random source `G`, which is
+/// the global [`random::rand`] by default.
+#[derive(Debug)]
+pub struct SkipList<K, V, G = Global> {
+    /// The first node of each level.
+    head: Vec<usize>,
+    nodes: Vec<Node<K, V>>,
+    /// The towers of the nodes, e.g. the next node on each level.
+    links: Vec<usize>,
+    /// The removed nodes for each level.
+    free: Vec<Vec<usize>>,
+    /// The buffer of [`prevs`] kept between the inserts and removes.
+    ///
+    /// [`prevs`]: SkipList::prevs
+    prevs: Vec<usize>,
+    len: usize,
+    max_level: usize,
+    p: f64,
+    rng: G,
+}
+
+impl<K, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V, G> Display for SkipList<K, V, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (level, head) in self.head.iter().enumerate() {
+            let mut next = *head;
+            while next != NIL {
+                write!(f, "{:?}", self.entry(next).0)?;
+                next = self.next(next, level);
+                if next != NIL {
+                    write!(f, ",")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: Ord, V, G> SkipList<K, V, G> {
+    /// Inserts the key/value and returns the old value in case
+    /// the key was already in the list.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        G: RandomSource,
+    {
+        let mut prevs = self.prevs(&key);
+        let next = self.next(prevs[0], 0);
+        if next != NIL {
+            if let Some((ref k, ref mut v)) = self.nodes[next].entry {
+                if *k == key {
+                    self.prevs = prevs;
+                    return Some(mem::replace(v, value));
+                }
+            }
+        }
+        let level = random_level(self.max_level, self.p, &mut self.rng);
+        if level > self.head.len() {
+            prevs.resize(level, NIL);
+            self.head.resize(level, NIL);
+        }
+        let node = self.alloc(key, value, level);
+        let tower = self.nodes[node].tower;
+        for (i, &prev) in prevs.iter().enumerate().take(level) {
+            self.links[tower + i] = mem::replace(self.link_mut(prev, i), node);
+        }
+        self.prevs = prevs;
+        self.len += 1;
+        None
+    }
+
+    /// Removes the key and returns the value in case it was in the list.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let prevs = self.prevs(key);
+        let node = self.next(prevs[0], 0);
+        if node == NIL || self.entry(node).0.borrow() != key {
+            self.prevs = prevs;
+            return None;
+        }
+        let (tower, level) = (self.nodes[node].tower, self.level(node));
+        for (i, &prev) in prevs.iter().enumerate().take(level) {
+            *self.link_mut(prev, i) = mem::replace(&mut self.links[tower + i], NIL);
+        }
+        self.prevs = prevs;
+        while let Some(&NIL) = self.head.last() {
+            self.head.pop();
+        }
+        self.free[level - 1].push(node);
+        self.len -= 1;
+        self.nodes[node].entry.take().map(|(_, value)| value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.find(key) {
+            NIL => None,
+            node => Some(&self.entry(node).1),
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.find(key) {
+            NIL => None,
+            node => self.nodes[node].entry.as_mut().map(|(_, value)| value),
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key) != NIL
+    }
+
+    /// Returns the sorted iterator over the entries within the range.
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let mut next = match range.start_bound() {
+            Bound::Included(start) => self.lower_bound(start),
+            Bound::Excluded(start) => self.upper_bound(start),
+            Bound::Unbounded => self.next(NIL, 0),
+        };
+        // the first node after the range.
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.upper_bound(end),
+            Bound::Excluded(end) => self.lower_bound(end),
+            Bound::Unbounded => NIL,
+        };
+        if next != NIL && !range.contains(self.entry(next).0.borrow()) {
+            // the start is after the end.
+            next = end;
+        }
+        Iter {
+            nodes: &self.nodes,
+            links: &self.links,
+            next,
+            end,
+        }
+    }
+
+    /// Returns the node of the key or `NIL`.
+    fn find<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.lower_bound(key) {
+            NIL => NIL,
+            node if self.entry(node).0.borrow() == key => node,
+            _ => NIL,
+        }
+    }
+
+    /// Returns the first node not before the key or `NIL`.
+    fn lower_bound<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.first_after(|k| k.borrow() < key)
+    }
+
+    /// Returns the first node after the key or `NIL`.
+    fn upper_bound<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.first_after(|k| k.borrow() <= key)
+    }
+
+    /// Returns the first node of which key is not `before`, without
+    /// keeping the previous nodes as [`prevs`] does.
+    ///
+    /// [`prevs`]: SkipList::prevs
+    fn first_after(&self, before: impl Fn(&K) -> bool) -> usize {
+        let mut prev = NIL;
+        for level in (0..self.head.len()).rev() {
+            loop {
+                let next = self.next(prev, level);
+                if next == NIL || !before(&self.entry(next).0) {
+                    break;
+                }
+                prev = next;
+            }
+        }
+        self.next(prev, 0)
+    }
+
+    /// Returns the last node before the key on each level, where `NIL`
+    /// is the head, with at least one level even for the empty list.
+    ///
+    /// The buffer is taken from the list, and is to be given back once
+    /// done with.
+    fn prevs<Q>(&mut self, key: &Q) -> Vec<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut prevs = mem::take(&mut self.prevs);
+        prevs.clear();
+        prevs.resize(std::cmp::max(self.head.len(), 1), NIL);
+        let mut prev = NIL;
+        for level in (0..self.head.len()).rev() {
+            loop {
+                let next = self.next(prev, level);
+                if next == NIL || self.entry(next).0.borrow() >= key {
+                    break;
+                }
+                prev = next;
+            }
+            prevs[level] = prev;
+        }
+        prevs
+    }
+}
+
+impl<K, V> SkipList<K, V> {
+    pub const fn new() -> Self {
+        Self {
+            head: Vec::new(),
+            nodes: Vec::new(),
+            links: Vec::new(),
+            free: Vec::new(),
+            prevs: Vec::new(),
+            len: 0,
+            max_level: MAX_LEVEL,
+            p: P,
+            rng: Global,
+        }
+    }
+
+    /// Creates a skip list of up to `max_level` levels, of which node is
+    /// promoted to the next level with the probability `p`.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `max_level` is zero or `p` is not between 0 and 1.
+    pub fn with_config(max_level: usize, p: f64) -> Self {
+        Self::with_config_and_rng(max_level, p, Global)
+    }
+}
+
+impl<K, V, G: RandomSource> SkipList<K, V, G> {
+    /// Creates a skip list of which levels are drawn from the `rng`,
+    /// e.g. the seeded one to reproduce the shape of the list.
+    pub fn with_rng(rng: G) -> Self {
+        Self::with_config_and_rng(MAX_LEVEL, P, rng)
+    }
+
+    /// Creates a skip list of [`with_config`] with the `rng`.
+    ///
+    /// [`with_config`]: SkipList::with_config
+    pub fn with_config_and_rng(max_level: usize, p: f64, rng: G) -> Self {
+        assert!(max_level > 0, "zero max level");
+        assert!(p > 0.0 && p < 1.0, "invalid probability: {p}");
+        Self {
+            head: Vec::new(),
+            nodes: Vec::new(),
+            links: Vec::new(),
+            free: Vec::new(),
+            prevs: Vec::new(),
+            len: 0,
+            max_level,
+            p,
+            rng,
+        }
+    }
+}
+
+impl<K, V, G> SkipList<K, V, G> {
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the current number of levels.
+    pub fn levels(&self) -> usize {
+        self.head.len()
+    }
+
+    /// Returns the sorted iterator over the entries.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            nodes: &self.nodes,
+            links: &self.links,
+            next: self.next(NIL, 0),
+            end: NIL,
+        }
+    }
+
+    /// Returns the node for the key/value, reusing the removed node
+    /// of the level if any.
+    fn alloc(&mut self, key: K, value: V, level: usize) -> usize {
+        if self.free.len() < level {
+            self.free.resize_with(level, Vec::new);
+        }
+        if let Some(node) = self.free[level - 1].pop() {
+            self.nodes[node].entry = Some((key, value));
+            return node;
+        }
+        self.nodes.push(Node {
+            entry: Some((key, value)),
+            tower: self.links.len(),
+        });
+        self.links.resize(self.links.len() + level, NIL);
+        self.nodes.len() - 1
+    }
+
+    fn level(&self, node: usize) -> usize {
+        let end = match self.nodes.get(node + 1) {
+            Some(next) => next.tower,
+            None => self.links.len(),
+        };
+        end - self.nodes[node].tower
+    }
+
+    fn entry(&self, node: usize) -> &(K, V) {
+        self.nodes[node]
+            .entry
+            .as_ref()
+            .expect("link to removed node")
+    }
+
+    /// Returns the next node of the `prev` on the level.
+    fn next(&self, prev: usize, level: usize) -> usize {
+        match prev {
+            NIL => self.head.get(level).copied().unwrap_or(NIL),
+            prev => self.links[self.nodes[prev].tower + level],
+        }
+    }
+
+    fn link_mut(&mut self, prev: usize, level: usize) -> &mut usize {
+        match prev {
+            NIL => &mut self.head[level],
+            prev => &mut self.links[self.nodes[prev].tower + level],
+        }
+    }
+}
+
+/// Returns the random level up to `max_level`, promoted to the next level
+/// with the probability `p`.
+fn random_level<G: RandomSource>(max_level: usize, p: f64, rng: &mut G) -> usize {
+    let threshold = (p * PRECISION as f64) as usize;
+    let mut level = 1;
+    while level < max_level && rng.rand(PRECISION) < threshold {
+        level += 1;
+    }
+    level
+}
+
+/// A sorted iterator over the entries of [`SkipList`].
+#[derive(Debug)]
+pub struct Iter<'a, K, V> {
+    nodes: &'a [Node<K, V>],
+    links: &'a [usize],
+    next: usize,
+    /// The first node after the entries, or `NIL`.
+    end: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.end {
+            return None;
+        }
+        let node = &self.nodes[self.next];
+        let (key, value) = node.entry.as_ref().expect("link to removed node");
+        self.next = self.links[node.tower];
+        Some((key, value))
+    }
+}
+
+#[derive(Debug)]
+struct Node<K, V> {
+    /// The key/value, or `None` once removed.
+    entry: Option<(K, V)>,
+    /// The start of the tower in the links, which ends at the start of
+    /// the next node in the arena.
+    tower: usize,
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,276 @@
+use super::{random_level, RandGen, RandomSource, SkipList, NIL, PRECISION};
+
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn list_from(keys: &[i32]) -> SkipList<i32, i32> {
+    let mut list = SkipList::new();
+    for k in keys {
+        list.insert(*k, k * 10);
+    }
+    list
+}
+
+#[test]
+fn test_insert_get() {
+    let mut list = list_from(&[4, 6, 77, 84, 27, 1]);
+    assert_eq!(list.len(), 6);
+    assert!(!list.is_empty());
+    assert_eq!(list.get(&27), Some(&270));
+    assert_eq!(list.get(&28), None);
+    assert!(list.contains_key(&84));
+    assert!(!list.contains_key(&85));
+    assert_eq!(list.insert(27, 1), Some(270));
+    assert_eq!(list.len(), 6);
+    *list.get_mut(&27).unwrap() += 1;
+    assert_eq!(list.get(&27), Some(&2));
+    assert_eq!(list.get_mut(&28), None);
+}
+
+#[test]
+fn test_borrowed_key() {
+    let mut list = SkipList::new();
+    list.insert("keith".to_string(), 18);
+    list.insert("dave".to_string(), 1);
+    assert_eq!(list.get("keith"), Some(&18));
+    assert_eq!(
+        list.range::<str, _>((Bound::Included("e"), Bound::Unbounded))
+            .count(),
+        1
+    );
+    assert_eq!(list.remove("dave"), Some(1));
+    assert!(!list.contains_key("dave"));
+}
+
+#[test]
+fn test_remove() {
+    let mut list = list_from(&[4, 6, 77, 84, 27, 1]);
+    assert_eq!(list.remove(&6), Some(60));
+    assert_eq!(list.remove(&6), None);
+    assert_eq!(list.remove(&1), Some(10));
+    assert_eq!(list.remove(&84), Some(840));
+    assert_eq!(list.len(), 3);
+    let keys: Vec<_> = list.iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec![4, 27, 77]);
+    for k in keys {
+        list.remove(&k);
+    }
+    assert!(list.is_empty());
+    assert_eq!(list.levels(), 0);
+}
+
+#[test]
+fn test_range() {
+    let list = list_from(&[4, 6, 77, 84, 27, 1]);
+    let keys =
+        |r: (Bound<i32>, Bound<i32>)| -> Vec<i32> { list.range(r).map(|(k, _)| *k).collect() };
+    assert_eq!(
+        keys((Bound::Included(4), Bound::Excluded(77))),
+        vec![4, 6, 27]
+    );
+    assert_eq!(
+        keys((Bound::Excluded(4), Bound::Included(77))),
+        vec![6, 27, 77]
+    );
+    assert_eq!(keys((Bound::Unbounded, Bound::Excluded(5))), vec![1, 4]);
+    assert_eq!(keys((Bound::Excluded(84), Bound::Unbounded)), vec![]);
+    assert_eq!(list.range(7..27).count(), 0);
+    assert_eq!(keys((Bound::Included(77), Bound::Excluded(6))), vec![]);
+    assert_eq!(keys((Bound::Excluded(6), Bound::Excluded(6))), vec![]);
+    assert_eq!(keys((Bound::Included(6), Bound::Included(6))), vec![6]);
+}
+
+#[test]
+fn test_range_without_clone() {
+    // the keys without `Clone`, and the borrowed bounds of the keys.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Key(String);
+
+    impl Borrow<str> for Key {
+        fn borrow(&self) -> &str {
+            &self.0
+        }
+    }
+
+    let mut list = SkipList::new();
+    for name in ["dave", "keith", "ann", "zoe"] {
+        list.insert(Key(name.to_string()), name.len());
+    }
+    let names: Vec<_> = list
+        .range::<str, _>((Bound::Included("b"), Bound::Excluded("l")))
+        .map(|(k, _)| &k.0[..])
+        .collect();
+    assert_eq!(names, vec!["dave", "keith"]);
+    let names: Vec<_> = list
+        .range(Key("dave".to_string())..)
+        .map(|(_, v)| *v)
+        .collect();
+    assert_eq!(names, vec![4, 5, 3]);
+}
+
+#[test]
+fn test_reuse_removed_nodes() {
+    let mut list = SkipList::new();
+    for k in 0..1_000 {
+        list.insert(k, ());
+    }
+    let capacity = list.nodes.len();
+    for _ in 0..10 {
+        for k in 0..1_000 {
+            list.remove(&k);
+        }
+        for k in 0..1_000 {
+            list.insert(k, ());
+        }
+    }
+    // only the nodes of the levels never removed before are allocated.
+    assert!(list.nodes.len() < capacity * 2);
+}
+
+#[test]
+fn test_drop_long_list() {
+    let mut list = SkipList::new();
+    for k in 0..1_000_000 {
+        list.insert(k, k);
+    }
+    assert_eq!(list.len(), 1_000_000);
+    drop(list);
+}
+
+#[test]
+fn test_with_config() {
+    let mut list = SkipList::with_config(4, 0.9);
+    for k in 0..1_000 {
+        list.insert(k, ());
+    }
+    assert!(list.levels() <= 4);
+    assert!(list.iter().map(|(k, _)| *k).eq(0..1_000));
+}
+
+#[test]
+#[should_panic(expected = "invalid probability")]
+fn test_with_config_invalid_probability() {
+    SkipList::<i32, i32>::with_config(4, 1.0);
+}
+
+#[test]
+fn test_against_btree_map() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut list = SkipList::new();
+    let mut oracle = BTreeMap::new();
+
+    for _ in 0..10_000 {
+        let k = rng.gen_range(0..500);
+        match rng.gen_range(0..4) {
+            0 | 1 => assert_eq!(list.insert(k, k * 10), oracle.insert(k, k * 10)),
+            2 => assert_eq!(list.remove(&k), oracle.remove(&k)),
+            _ => {
+                assert_eq!(list.get(&k), oracle.get(&k));
+                let end = k + rng.gen_range(0..50);
+                assert!(list.range(k..end).eq(oracle.range(k..end)));
+            }
+        }
+        assert_eq!(list.len(), oracle.len());
+    }
+    assert!(list.iter().eq(oracle.iter()));
+}
+
+/// A source always returning the same number.
+struct Fixed(usize);
+
+impl RandomSource for Fixed {
+    fn rand(&mut self, max: usize) -> usize {
+        self.0 % max
+    }
+}
+
+/// Returns the number of the nodes on each level.
+fn level_counts<G>(list: &SkipList<i32, (), G>) -> Vec<usize> {
+    (0..list.levels())
+        .map(|level| {
+            let mut count = 0;
+            let mut node = list.next(NIL, level);
+            while node != NIL {
+                count += 1;
+                node = list.next(node, level);
+            }
+            count
+        })
+        .collect()
+}
+
+/// Checks the levels drawn from the source follow the geometric
+/// distribution of `p`, within 5 standard deviations on each level.
+fn check_geometric<G: RandomSource>(mut rng: G, p: f64) {
+    const N: usize = 100_000;
+    const MAX_LEVEL: usize = 16;
+
+    let mut counts = [0; MAX_LEVEL + 1];
+    for _ in 0..N {
+        counts[random_level(MAX_LEVEL, p, &mut rng)] += 1;
+    }
+    for (level, &count) in counts.iter().enumerate().skip(1) {
+        let prob = if level == MAX_LEVEL {
+            p.powi(level as i32 - 1)
+        } else {
+            p.powi(level as i32 - 1) * (1.0 - p)
+        };
+        let expected = N as f64 * prob;
+        let sd = (expected * (1.0 - prob)).sqrt();
+        assert!(
+            (count as f64 - expected).abs() <= 5.0 * sd + 1.0,
+            "level {level} of p = {p}: {count} vs {expected:.1}"
+        );
+    }
+}
+
+#[test]
+fn test_level_distribution() {
+    for p in [0.5, 0.25, 0.75] {
+        check_geometric(StdRng::seed_from_u64(55), p);
+        check_geometric(RandGen::new(55), p);
+    }
+}
+
+#[test]
+fn test_fixed_source() {
+    let mut list = SkipList::with_config_and_rng(5, 0.5, Fixed(0));
+    for k in 0..10 {
+        list.insert(k, ());
+    }
+    assert_eq!(level_counts(&list), vec![10; 5]);
+
+    let mut list = SkipList::with_rng(Fixed(PRECISION - 1));
+    for k in 0..10 {
+        list.insert(k, ());
+    }
+    assert_eq!(level_counts(&list), vec![10]);
+}
+
+#[test]
+fn test_with_rng_reproducible() {
+    let shape = |seed| {
+        let mut list = SkipList::with_rng(StdRng::seed_from_u64(seed));
+        for k in 0..1_000 {
+            list.insert(k, ());
+        }
+        level_counts(&list)
+    };
+    assert_eq!(shape(55), shape(55));
+    assert_ne!(shape(55), shape(56));
+
+    let mut list = SkipList::with_rng(RandGen::new(55));
+    for k in 0..10_000 {
+        list.insert(k, ());
+    }
+    // halves on each level.
+    let counts = level_counts(&list);
+    for pair in counts.windows(2).take(6) {
+        let ratio = pair[1] as f64 / pair[0] as f64;
+        assert!((0.4..0.6).contains(&ratio), "{counts:?}");
+    }
+}
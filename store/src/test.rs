@@ -43,3 +43,32 @@ fn test_insert_only() {
     assert!(b3.get::<&str, String>(&"green").is_err());
     assert!(b3.get::<&str, String>(&"fish").is_ok());
 }
+
+#[test]
+fn test_hmap_round_trip() {
+    use hash_map::HMap;
+
+    let file = "test_hmap_round_trip";
+    fs::remove_file(file).ok();
+    let mut map = HMap::new();
+    for (i, name) in ["keith", "dave", "andy", "pete", "jane", "sam"]
+        .iter()
+        .enumerate()
+    {
+        map.insert(name.to_string(), i as i32);
+    }
+    let mut bs = Store::new_or_open(file, 1000, 10).unwrap();
+    bs.insert("names", &map).unwrap();
+    drop(bs);
+
+    let mut bs = Store::open(file).unwrap();
+    let got: HMap<String, i32> = bs
+        .get::<&str, ()>(&"names")
+        .unwrap()
+        .get_v::<HMap<String, i32>>()
+        .unwrap();
+    assert_eq!(got.len(), map.len());
+    for (k, v) in map.iter() {
+        assert_eq!(got.get(k), Some(v));
+    }
+}
@@ -0,0 +1,425 @@
+//! A binary tree.
+
+#![forbid(missing_debug_implementations)]
+
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Display};
+use std::mem;
+use std::ops::ControlFlow;
+
+use render::Render;
+
+/// A binary search tree.
+#[derive(Debug)]
+pub struct Tree<T: Debug> {
+    root: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+impl<T: Debug> Display for Tree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flow = self.visit(Order::In, |data, depth| {
+            match writeln!(f, "{:.<depth$}{:?}", "", data) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(e) => ControlFlow::Break(e),
+            }
+        });
+        match flow {
+            ControlFlow::Break(e) => Err(e),
+            ControlFlow::Continue(()) => Ok(()),
+        }
+    }
+}
+
+impl<T: Debug> Default for Tree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Drop for Tree<T> {
+    fn drop(&mut self) {
+        drop_link(self.root.take());
+    }
+}
+
+impl<T: Debug + PartialOrd> FromIterator<T> for Tree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        for data in iter {
+            tree.insert(data);
+        }
+        tree
+    }
+}
+
+impl<'a, T: Debug> IntoIterator for &'a Tree<T> {
+    type Item = &'a T;
+    type IntoIter = Traverse<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Debug> IntoIterator for Tree<T> {
+    type Item = T;
+    type IntoIter = IntoTraverse<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_traverse(Order::In)
+    }
+}
+
+impl<T: Debug> Tree<T> {
+    pub const fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the number of the nodes.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of the nodes on the longest path from the root,
+    /// which is zero for the empty tree.
+    pub fn height(&self) -> usize {
+        // the last node in the level order is on the deepest level.
+        let mut traverse = self.traverse(Order::Level);
+        let mut height = 0;
+        while let Some((_, depth)) = traverse.next_with_depth() {
+            height = depth + 1;
+        }
+        height
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(ref left) = node.left {
+            node = left;
+        }
+        Some(&node.data)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(ref right) = node.right {
+            node = right;
+        }
+        Some(&node.data)
+    }
+
+    /// Returns the in-order iterator.
+    pub fn iter(&self) -> Traverse<'_, T> {
+        self.traverse(Order::In)
+    }
+
+    /// Returns the iterator visiting the nodes in the `order`.
+    pub fn traverse(&self, order: Order) -> Traverse<'_, T> {
+        Traverse {
+            order,
+            steps: self
+                .root
+                .iter()
+                .map(|node| Step::Expand(&**node, 0))
+                .collect(),
+        }
+    }
+
+    /// Calls the `visitor` with the data and the depth of each node in the
+    /// `order`, e.g. zero for the root, until it breaks.
+    pub fn visit<B, F>(&self, order: Order, mut visitor: F) -> ControlFlow<B>
+    where
+        F: FnMut(&T, usize) -> ControlFlow<B>,
+    {
+        let mut traverse = self.traverse(order);
+        while let Some((data, depth)) = traverse.next_with_depth() {
+            if let ControlFlow::Break(b) = visitor(data, depth) {
+                return ControlFlow::Break(b);
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Returns the consuming iterator visiting the nodes in the `order`.
+    pub fn into_traverse(mut self, order: Order) -> IntoTraverse<T> {
+        IntoTraverse {
+            order,
+            steps: self.root.take().into_iter().map(IntoStep::Expand).collect(),
+        }
+    }
+
+    /// Renders the tree in the Graphviz DOT language.
+    pub fn to_dot(&self) -> String {
+        render::dot(self.root.as_deref())
+    }
+
+    /// Renders the tree top-down with the box-drawing characters.
+    pub fn to_boxes(&self) -> String {
+        render::boxes(self.root.as_deref())
+    }
+}
+
+impl<T: Debug + PartialOrd> Tree<T> {
+    /// Inserts the data and returns `true` in case it was not in the tree.
+    pub fn insert(&mut self, data: T) -> bool {
+        let link = self.search(&data);
+        if link.is_some() {
+            return false;
+        }
+        *link = Some(Box::new(Node::from(data)));
+        self.len += 1;
+        true
+    }
+
+    pub fn contains(&self, data: &T) -> bool {
+        let mut link = &self.root;
+        while let Some(ref node) = link {
+            if *data < node.data {
+                link = &node.left;
+            } else if *data > node.data {
+                link = &node.right;
+            } else {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Removes the data and returns it in case it was in the tree.
+    pub fn remove(&mut self, data: &T) -> Option<T> {
+        let data = remove_top(self.search(data))?;
+        self.len -= 1;
+        Some(data)
+    }
+
+    /// Returns the link to the node of the data, or the empty link where
+    /// the data would be inserted.
+    fn search(&mut self, data: &T) -> &mut Link<T> {
+        let mut link = &mut self.root;
+        while let Some(node) = link.as_deref() {
+            let left = if *data < node.data {
+                true
+            } else if *data > node.data {
+                false
+            } else {
+                break;
+            };
+            let node = link.as_mut().unwrap();
+            link = if left {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+        }
+        link
+    }
+}
+
+/// Removes the top node of the link and returns its data.
+fn remove_top<T: Debug>(link: &mut Link<T>) -> Option<T> {
+    let mut node = link.take()?;
+    match (node.left.take(), node.right.take()) {
+        (None, right) => *link = right,
+        (left, None) => *link = left,
+        (left, right) => {
+            // replaces the top with the next data.
+            node.left = left;
+            node.right = right;
+            let data = remove_min(&mut node.right)?;
+            let data = mem::replace(&mut node.data, data);
+            *link = Some(node);
+            return Some(data);
+        }
+    }
+    Some(node.data)
+}
+
+fn remove_min<T: Debug>(mut link: &mut Link<T>) -> Option<T> {
+    while link.as_ref()?.left.is_some() {
+        link = &mut link.as_mut()?.left;
+    }
+    let node = link.take()?;
+    let Node { data, right, .. } = *node;
+    *link = right;
+    Some(data)
+}
+
+/// Drops the nodes without the recursion by rotating the left child up
+/// until the top has none, and then moving on to the right one.
+fn drop_link<T: Debug>(mut link: Link<T>) {
+    while let Some(mut node) = link {
+        link = match node.left.take() {
+            Some(mut left) => {
+                node.left = left.right.take();
+                left.right = Some(node);
+                Some(left)
+            }
+            None => node.right.take(),
+        };
+    }
+}
+
+/// A tree traversal order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// The node before its left and right subtrees.
+    Pre,
+    /// The node between its left and right subtrees.
+    In,
+    /// The node after its left and right subtrees.
+    Post,
+    /// The nodes level by level from the root, e.g. breadth first.
+    Level,
+}
+
+/// An iterator over the data of [`Tree`] in the [`Order`].
+#[derive(Debug)]
+pub struct Traverse<'a, T: Debug> {
+    order: Order,
+    steps: VecDeque<Step<'a, T>>,
+}
+
+/// The steps carry the depths of the nodes.
+#[derive(Debug)]
+enum Step<'a, T: Debug> {
+    /// The node of which subtrees are not visited yet.
+    Expand(&'a Node<T>, usize),
+    Visit(&'a T, usize),
+}
+
+impl<'a, T: Debug> Traverse<'a, T> {
+    fn next_with_depth(&mut self) -> Option<(&'a T, usize)> {
+        loop {
+            if self.order == Order::Level {
+                let (node, depth) = match self.steps.pop_front()? {
+                    Step::Expand(node, depth) => (node, depth),
+                    Step::Visit(data, depth) => return Some((data, depth)),
+                };
+                for child in [&node.left, &node.right].into_iter().flatten() {
+                    self.steps.push_back(Step::Expand(child, depth + 1));
+                }
+                return Some((&node.data, depth));
+            }
+            let (node, depth) = match self.steps.pop_back()? {
+                Step::Expand(node, depth) => (node, depth),
+                Step::Visit(data, depth) => return Some((data, depth)),
+            };
+            // pushes the steps in the reverse order.
+            let expand = |child: &'a Link<T>| child.as_deref().map(|c| Step::Expand(c, depth + 1));
+            let left = expand(&node.left);
+            let right = expand(&node.right);
+            let data = Some(Step::Visit(&node.data, depth));
+            let steps = match self.order {
+                Order::Pre => [right, left, data],
+                Order::In => [right, data, left],
+                _ => [data, right, left],
+            };
+            self.steps.extend(steps.into_iter().flatten());
+        }
+    }
+}
+
+impl<'a, T: Debug> Iterator for Traverse<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_depth().map(|(data, _)| data)
+    }
+}
+
+/// A consuming iterator over the data of [`Tree`] in the [`Order`].
+#[derive(Debug)]
+pub struct IntoTraverse<T: Debug> {
+    order: Order,
+    steps: VecDeque<IntoStep<T>>,
+}
+
+#[derive(Debug)]
+enum IntoStep<T: Debug> {
+    Expand(Box<Node<T>>),
+    Visit(T),
+}
+
+impl<T: Debug> Drop for IntoTraverse<T> {
+    fn drop(&mut self) {
+        for step in self.steps.drain(..) {
+            if let IntoStep::Expand(node) = step {
+                drop_link(Some(node));
+            }
+        }
+    }
+}
+
+impl<T: Debug> Iterator for IntoTraverse<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.order == Order::Level {
+                let node = match self.steps.pop_front()? {
+                    IntoStep::Expand(node) => node,
+                    IntoStep::Visit(data) => return Some(data),
+                };
+                let Node { data, left, right } = *node;
+                self.steps
+                    .extend([left, right].into_iter().flatten().map(IntoStep::Expand));
+                return Some(data);
+            }
+            let node = match self.steps.pop_back()? {
+                IntoStep::Expand(node) => node,
+                IntoStep::Visit(data) => return Some(data),
+            };
+            let Node { data, left, right } = *node;
+            let left = left.map(IntoStep::Expand);
+            let right = right.map(IntoStep::Expand);
+            let data = Some(IntoStep::Visit(data));
+            let steps = match self.order {
+                Order::Pre => [right, left, data],
+                Order::In => [right, data, left],
+                _ => [data, right, left],
+            };
+            self.steps.extend(steps.into_iter().flatten());
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Node<T: Debug> {
+    data: T,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T: Debug> From<T> for Node<T> {
+    fn from(data: T) -> Self {
+        Self {
+            data,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+impl<T: Debug> Render for Node<T> {
+    fn label(&self) -> String {
+        format!("{:?}", self.data)
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,182 @@
+use super::{Node, Order, Tree};
+
+use std::collections::BTreeSet;
+use std::ops::ControlFlow;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+//     4
+//    / \
+//   1   5
+//    \   \
+//     3   6
+//          \
+//           10
+//             \
+//              94
+//             /
+//            54
+fn tree() -> Tree<i32> {
+    [4, 5, 6, 10, 1, 94, 54, 3].into_iter().collect()
+}
+
+#[test]
+fn test_traverse() {
+    let tree = tree();
+    let walk = |order| tree.traverse(order).copied().collect::<Vec<_>>();
+    assert_eq!(walk(Order::Pre), vec![4, 1, 3, 5, 6, 10, 94, 54]);
+    assert_eq!(walk(Order::In), vec![1, 3, 4, 5, 6, 10, 54, 94]);
+    assert_eq!(walk(Order::Post), vec![3, 1, 54, 94, 10, 6, 5, 4]);
+    assert_eq!(walk(Order::Level), vec![4, 1, 5, 3, 6, 10, 94, 54]);
+    assert!(tree.iter().eq((&tree).into_iter()));
+}
+
+#[test]
+fn test_into_traverse() {
+    for order in [Order::Pre, Order::In, Order::Post, Order::Level] {
+        let tree = tree();
+        let expected: Vec<_> = tree.traverse(order).copied().collect();
+        assert_eq!(tree.into_traverse(order).collect::<Vec<_>>(), expected);
+    }
+    assert_eq!(
+        tree().into_iter().collect::<Vec<_>>(),
+        vec![1, 3, 4, 5, 6, 10, 54, 94]
+    );
+}
+
+#[test]
+fn test_empty() {
+    let tree = Tree::<i32>::new();
+    assert!(tree.is_empty());
+    assert_eq!(tree.len(), 0);
+    assert_eq!(tree.height(), 0);
+    assert_eq!(tree.min(), None);
+    assert_eq!(tree.max(), None);
+    for order in [Order::Pre, Order::In, Order::Post, Order::Level] {
+        assert_eq!(tree.traverse(order).next(), None);
+    }
+}
+
+#[test]
+fn test_insert_contains() {
+    let mut tree = tree();
+    assert_eq!(tree.len(), 8);
+    assert_eq!(tree.height(), 6);
+    assert!(tree.contains(&54));
+    assert!(!tree.contains(&55));
+    assert!(!tree.insert(54));
+    assert!(tree.insert(55));
+    assert_eq!(tree.len(), 9);
+    assert_eq!(tree.min(), Some(&1));
+    assert_eq!(tree.max(), Some(&94));
+}
+
+#[test]
+fn test_remove() {
+    let mut tree = tree();
+    assert_eq!(tree.remove(&4), Some(4));
+    assert_eq!(tree.remove(&4), None);
+    assert_eq!(tree.remove(&10), Some(10));
+    assert_eq!(tree.remove(&1), Some(1));
+    assert_eq!(
+        tree.iter().copied().collect::<Vec<_>>(),
+        vec![3, 5, 6, 54, 94]
+    );
+    assert_eq!(tree.traverse(Order::Pre).next(), Some(&5));
+}
+
+#[test]
+fn test_visit() {
+    let tree = tree();
+    let mut visited = Vec::new();
+    let flow = tree.visit(Order::Pre, |&data, depth| {
+        visited.push((data, depth));
+        if data == 6 {
+            return ControlFlow::Break(depth);
+        }
+        ControlFlow::Continue(())
+    });
+    assert_eq!(flow, ControlFlow::Break(2));
+    assert_eq!(visited, vec![(4, 0), (1, 1), (3, 2), (5, 1), (6, 2)]);
+    assert_eq!(
+        format!("{tree}"),
+        ".1\n..3\n4\n.5\n..6\n...10\n.....54\n....94\n"
+    );
+}
+
+/// Returns the tree of the sorted `0..n` in O(n), which is a list as deep
+/// as it is long.
+fn sorted(n: usize) -> Tree<usize> {
+    let mut root = None;
+    for data in (0..n).rev() {
+        let mut node = Box::new(Node::from(data));
+        node.right = root;
+        root = Some(node);
+    }
+    Tree { root, len: n }
+}
+
+#[test]
+fn test_sorted() {
+    const N: usize = 200_000;
+    let mut tree = sorted(N);
+    assert_eq!(tree.height(), N);
+    assert!(tree.iter().copied().eq(0..N));
+    assert!(tree.insert(N));
+    assert_eq!(tree.remove(&(N / 2)), Some(N / 2));
+    assert_eq!(tree.remove(&0), Some(0));
+    assert_eq!(tree.len(), N - 1);
+    assert_eq!(tree.max(), Some(&N));
+    assert_eq!(tree.to_dot().lines().count(), 2 + tree.len() * 2 - 1);
+    let mut into = sorted(N).into_traverse(Order::Post);
+    assert_eq!(into.next(), Some(N - 1));
+    drop(into);
+    drop(tree);
+
+    let tree: Tree<_> = (0..10_000).rev().collect();
+    assert_eq!(tree.height(), 10_000);
+    assert_eq!(tree.min(), Some(&0));
+}
+
+#[test]
+fn test_render() {
+    assert_eq!(
+        tree().to_boxes(),
+        "\
+┌───4─┐
+1─┐   5─┐
+  3     6──┐
+          10─────┐
+              ┌─94
+             54
+"
+    );
+    let dot = tree().to_dot();
+    assert!(dot.starts_with("digraph {\n    n0 [label=\"4\"];\n"));
+    assert!(dot.contains("n0:sw -> n1;"));
+    assert_eq!(Tree::<i32>::new().to_boxes(), "");
+}
+
+#[test]
+fn test_against_btree_set() {
+    let mut rng = StdRng::seed_from_u64(55);
+    let mut tree = Tree::new();
+    let mut oracle = BTreeSet::new();
+
+    for _ in 0..5_000 {
+        let k = rng.gen_range(0..200);
+        match rng.gen_range(0..3) {
+            0 => assert_eq!(tree.insert(k), oracle.insert(k)),
+            1 => assert_eq!(tree.remove(&k), oracle.take(&k)),
+            _ => assert_eq!(tree.contains(&k), oracle.contains(&k)),
+        }
+    }
+    assert!(tree.iter().eq(oracle.iter()));
+    assert_eq!(tree.len(), oracle.len());
+    assert_eq!(tree.min(), oracle.iter().next());
+    assert_eq!(tree.max(), oracle.iter().next_back());
+    let mut pre: Vec<_> = tree.traverse(Order::Pre).collect();
+    pre.sort();
+    assert!(pre.into_iter().eq(oracle.iter()));
+}